@@ -87,6 +87,15 @@ pub enum Account {
         #[clap(short = 'r', long)]
         raw: bool,
     },
+    /// Benchmarks the generation of Aleo accounts, reporting the number of accounts generated per second
+    Bench {
+        /// Specify the network of the accounts
+        #[clap(default_value = "0", long = "network")]
+        network: u16,
+        /// The number of accounts to generate
+        #[clap(default_value = "1000", short = 'n', long)]
+        num: usize,
+    },
 }
 
 /// Parse a raw Aleo input into fields
@@ -150,6 +159,16 @@ impl Account {
                     unknown_id => bail!("Unknown network ID ({unknown_id})"),
                 }
             }
+            Self::Bench { network, num } => {
+                // Benchmark the account generation for the specified network.
+                let rate = match network {
+                    MainnetV0::ID => Self::bench::<MainnetV0>(num)?,
+                    TestnetV0::ID => Self::bench::<TestnetV0>(num)?,
+                    CanaryV0::ID => Self::bench::<CanaryV0>(num)?,
+                    unknown_id => bail!("Unknown network ID ({unknown_id})"),
+                };
+                Ok(format!("Generated {num} accounts ({rate:.2} accounts/s)"))
+            }
         }
     }
 
@@ -261,6 +280,25 @@ impl Account {
         Ok(account_info)
     }
 
+    /// Generates the given number of Aleo accounts with a fresh RNG, returning the number of accounts per second.
+    fn bench<N: Network>(num: usize) -> Result<f64> {
+        // Ensure at least one account is generated.
+        if num == 0 {
+            bail!("The number of accounts to generate must be at least 1");
+        }
+        // Initialize the RNG.
+        let mut rng = ChaChaRng::from_entropy();
+        // Initialize a timer.
+        let timer = std::time::Instant::now();
+        // Generate the accounts.
+        for _ in 0..num {
+            let private_key = PrivateKey::<N>::new(&mut rng)?;
+            let _address = Address::try_from(&private_key)?;
+        }
+        // Compute the rate, guarding against a zero elapsed time.
+        Ok(num as f64 / timer.elapsed().as_secs_f64().max(f64::MIN_POSITIVE))
+    }
+
     // Sign a message with an Aleo private key
     fn sign<N: Network>(key: String, message: String, raw: bool) -> Result<String> {
         // Sample a random field element.
@@ -394,6 +432,20 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn test_bench() {
+        use snarkvm::console::network::MainnetV0;
+
+        // Ensure the benchmark produces a positive rate.
+        assert!(Account::bench::<MainnetV0>(10).unwrap() > 0.0);
+        // Ensure the command runs.
+        let account = Account::Bench { network: 0, num: 10 };
+        assert!(account.parse().unwrap().starts_with("Generated 10 accounts"));
+        // Ensure at least one account must be generated.
+        let account = Account::Bench { network: 0, num: 0 };
+        assert!(account.parse().is_err());
+    }
+
     #[test]
     fn test_signature_raw() {
         let key = "APrivateKey1zkp61PAYmrYEKLtRWeWhUoDpFnGLNuHrCciSqN49T86dw3p".to_string();
@@ -483,4 +535,41 @@ mod tests {
         let actual = account.parse();
         assert!(actual.is_ok());
     }
+
+    #[test]
+    fn test_sign_and_verify_seeded() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaChaRng;
+        use snarkvm::console::network::MainnetV0;
+
+        // Sample the seeded accounts of the first two nodes (as in the `simple_node` example).
+        let node_0 = snarkos_account::Account::<MainnetV0>::new(&mut ChaChaRng::seed_from_u64(0)).unwrap();
+        let node_1 = snarkos_account::Account::<MainnetV0>::new(&mut ChaChaRng::seed_from_u64(1)).unwrap();
+
+        // Sign a message with the account of node 0.
+        let message = "Hello, world!".to_string();
+        let account = Account::Sign {
+            network: 0,
+            private_key: Some(node_0.private_key().to_string()),
+            private_key_file: None,
+            message: message.clone(),
+            raw: true,
+        };
+        let signature = account.parse().unwrap();
+
+        // Verify the signature against the address of node 0.
+        let account = Account::Verify {
+            network: 0,
+            address: node_0.address().to_string(),
+            signature: signature.clone(),
+            message: message.clone(),
+            raw: true,
+        };
+        assert!(account.parse().is_ok());
+
+        // Ensure the signature is invalid against the address of node 1.
+        let account =
+            Account::Verify { network: 0, address: node_1.address().to_string(), signature, message, raw: true };
+        assert!(account.parse().is_err());
+    }
 }
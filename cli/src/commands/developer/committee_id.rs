@@ -0,0 +1,126 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{
+    console::network::{CanaryV0, MainnetV0, Network, TestnetV0},
+    ledger::committee::Committee,
+    prelude::Address,
+};
+
+use anyhow::{Result, anyhow, bail};
+use clap::Parser;
+use indexmap::IndexMap;
+use std::str::FromStr;
+
+/// Derives the canonical ID of a committee from its members, to compare committee configurations.
+#[derive(Debug, Parser)]
+pub struct CommitteeId {
+    /// Specify the network of the committee.
+    #[clap(default_value = "0", long = "network")]
+    pub network: u16,
+    /// The starting round of the committee.
+    #[clap(default_value = "0", long = "round")]
+    pub round: u64,
+    /// The members of the committee, as `<ADDRESS>=<STAKE>` (in microcredits); may be repeated.
+    #[clap(long = "member", required = true)]
+    pub members: Vec<String>,
+}
+
+impl CommitteeId {
+    pub fn parse(self) -> Result<String> {
+        // Derive the committee ID for the given network.
+        match self.network {
+            MainnetV0::ID => Self::committee_id::<MainnetV0>(self.round, &self.members),
+            TestnetV0::ID => Self::committee_id::<TestnetV0>(self.round, &self.members),
+            CanaryV0::ID => Self::committee_id::<CanaryV0>(self.round, &self.members),
+            unknown_id => bail!("Unknown network ID ({unknown_id})"),
+        }
+    }
+
+    /// Parses the members, and returns the ID of the committee they form in the given round.
+    /// Note: The members are sorted by address, so that the ID does not depend on the order they are given in.
+    fn committee_id<N: Network>(round: u64, members: &[String]) -> Result<String> {
+        let mut parsed_members = Vec::with_capacity(members.len());
+        for member in members {
+            let (address, stake) = member
+                .split_once('=')
+                .ok_or_else(|| anyhow!("Invalid member '{member}', expected <ADDRESS>=<STAKE>"))?;
+            let address = Address::<N>::from_str(address.trim())?;
+            let stake = u64::from_str(stake.trim()).map_err(|e| anyhow!("Invalid stake in '{member}' - {e}"))?;
+            parsed_members.push((address.to_string(), address, stake));
+        }
+        parsed_members.sort_unstable_by(|(a, ..), (b, ..)| a.cmp(b));
+
+        let mut committee_members = IndexMap::with_capacity(parsed_members.len());
+        for (_, address, stake) in parsed_members {
+            if committee_members.insert(address, (stake, false, 0u8)).is_some() {
+                bail!("The member {address} is given more than once");
+            }
+        }
+        // Construct the committee, and return its ID.
+        Ok(Committee::<N>::new(round, committee_members)?.id().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use snarkvm::{
+        ledger::committee::MIN_VALIDATOR_STAKE,
+        prelude::{PrivateKey, TestRng},
+    };
+
+    type CurrentNetwork = MainnetV0;
+
+    /// Returns the members of a fixed committee of four validators, sampled from a seeded RNG.
+    fn sample_members() -> Vec<String> {
+        let mut rng = TestRng::fixed(1234567890);
+        (0..4)
+            .map(|i| {
+                let address = Address::<CurrentNetwork>::try_from(PrivateKey::new(&mut rng).unwrap()).unwrap();
+                format!("{address}={}", MIN_VALIDATOR_STAKE * (i + 1))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_committee_id_is_stable() {
+        let members = sample_members();
+        let id = CommitteeId { network: 0, round: 0, members: members.clone() }.parse().unwrap();
+        // Ensure the ID is the same when derived again.
+        assert_eq!(CommitteeId { network: 0, round: 0, members: members.clone() }.parse().unwrap(), id);
+        // Ensure the ID does not depend on the order of the members.
+        let reversed = members.iter().rev().cloned().collect();
+        assert_eq!(CommitteeId { network: 0, round: 0, members: reversed }.parse().unwrap(), id);
+        // Ensure the ID changes with the round.
+        assert_ne!(CommitteeId { network: 0, round: 2, members: members.clone() }.parse().unwrap(), id);
+        // Ensure the ID changes with the stakes.
+        let mut restaked = members;
+        restaked[0] = restaked[0].replace(&format!("={MIN_VALIDATOR_STAKE}"), &format!("={}", MIN_VALIDATOR_STAKE * 5));
+        assert_ne!(CommitteeId { network: 0, round: 0, members: restaked }.parse().unwrap(), id);
+    }
+
+    #[test]
+    fn test_committee_id_invalid_members() {
+        let members = sample_members();
+        // Ensure a member without a stake fails.
+        let missing_stake = vec![members[0].split_once('=').unwrap().0.to_string()];
+        assert!(CommitteeId { network: 0, round: 0, members: missing_stake }.parse().is_err());
+        // Ensure a duplicate member fails.
+        let duplicate = vec![members[0].clone(), members[0].clone(), members[1].clone(), members[2].clone()];
+        assert!(CommitteeId { network: 0, round: 0, members: duplicate }.parse().is_err());
+    }
+}
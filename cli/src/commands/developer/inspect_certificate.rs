@@ -0,0 +1,143 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{
+    console::network::{CanaryV0, MainnetV0, Network, TestnetV0},
+    ledger::narwhal::BatchCertificate,
+    prelude::FromBytes,
+};
+
+use anyhow::{Result, bail};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use clap::Parser;
+use std::fmt::Write;
+
+/// Decodes and inspects a serialized batch certificate.
+#[derive(Debug, Parser)]
+pub struct InspectCertificate {
+    /// Specify the network of the certificate to inspect.
+    #[clap(default_value = "0", long = "network")]
+    pub network: u16,
+    /// The serialized certificate, as a hex (optionally `0x`-prefixed) or base64 string.
+    pub certificate: String,
+}
+
+impl InspectCertificate {
+    pub fn parse(self) -> Result<String> {
+        // Inspect the certificate for the given network.
+        match self.network {
+            MainnetV0::ID => Self::inspect_certificate::<MainnetV0>(&self.certificate),
+            TestnetV0::ID => Self::inspect_certificate::<TestnetV0>(&self.certificate),
+            CanaryV0::ID => Self::inspect_certificate::<CanaryV0>(&self.certificate),
+            unknown_id => bail!("Unknown network ID ({unknown_id})"),
+        }
+    }
+
+    /// Decodes the given blob, which is either hex or base64 encoded.
+    fn decode_blob(blob: &str) -> Result<Vec<u8>> {
+        let blob = blob.trim();
+        // Attempt to decode the blob as hex first, as a hex string may also be valid base64.
+        let hex_blob = blob.strip_prefix("0x").unwrap_or(blob);
+        if let Ok(bytes) = hex::decode(hex_blob) {
+            return Ok(bytes);
+        }
+        match STANDARD.decode(blob) {
+            Ok(bytes) => Ok(bytes),
+            Err(_) => bail!("The certificate is neither valid hex nor valid base64"),
+        }
+    }
+
+    /// Deserializes the certificate and formats its contents.
+    fn inspect_certificate<N: Network>(blob: &str) -> Result<String> {
+        // Deserialize the certificate.
+        let certificate = BatchCertificate::<N>::from_bytes_le(&Self::decode_blob(blob)?)?;
+
+        let mut output = String::new();
+        writeln!(output, "Certificate {}", certificate.id())?;
+        writeln!(output, "  round: {}", certificate.round())?;
+        writeln!(output, "  author: {}", certificate.author())?;
+        writeln!(output, "  batch ID: {}", certificate.batch_id())?;
+        writeln!(output, "  timestamp: {}", certificate.timestamp())?;
+        writeln!(output, "  transmission IDs ({}):", certificate.transmission_ids().len())?;
+        for transmission_id in certificate.transmission_ids() {
+            writeln!(output, "    {transmission_id}")?;
+        }
+        writeln!(output, "  previous certificate IDs ({}):", certificate.previous_certificate_ids().len())?;
+        for previous_certificate_id in certificate.previous_certificate_ids() {
+            writeln!(output, "    {previous_certificate_id}")?;
+        }
+        writeln!(output, "  signatures ({}):", certificate.signatures().len())?;
+        for signature in certificate.signatures() {
+            writeln!(output, "    {} - {signature}", signature.to_address())?;
+        }
+        Ok(output.trim_end().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indexmap::IndexSet;
+    use snarkvm::{
+        ledger::narwhal::BatchHeader,
+        prelude::{PrivateKey, TestRng, ToBytes},
+    };
+
+    type CurrentNetwork = MainnetV0;
+
+    /// Samples a certificate, signed by a second validator.
+    fn sample_certificate(rng: &mut TestRng) -> BatchCertificate<CurrentNetwork> {
+        let author = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let signer = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let header = BatchHeader::new(
+            &author,
+            1,
+            1_700_000_000,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            rng,
+        )
+        .unwrap();
+        let signature = signer.sign(&[header.batch_id()], rng).unwrap();
+        BatchCertificate::from(header, IndexSet::from([signature])).unwrap()
+    }
+
+    #[test]
+    fn test_inspect_certificate() {
+        let mut rng = TestRng::default();
+        let certificate = sample_certificate(&mut rng);
+        let bytes = certificate.to_bytes_le().unwrap();
+
+        // Inspect the certificate, encoded as hex, prefixed hex, and base64.
+        for blob in [hex::encode(&bytes), format!("0x{}", hex::encode(&bytes)), STANDARD.encode(&bytes)] {
+            let inspect = InspectCertificate { network: 0, certificate: blob };
+            let output = inspect.parse().unwrap();
+            assert!(output.starts_with(&format!("Certificate {}", certificate.id())));
+            assert!(output.contains(&format!("round: {}", certificate.round())));
+            assert!(output.contains(&format!("author: {}", certificate.author())));
+            assert!(output.contains("signatures (1):"));
+        }
+    }
+
+    #[test]
+    fn test_inspect_invalid_certificate() {
+        // Ensure an undecodable blob fails.
+        assert!(InspectCertificate { network: 0, certificate: "not a certificate!".to_string() }.parse().is_err());
+        // Ensure a decodable blob that is not a certificate fails.
+        assert!(InspectCertificate { network: 0, certificate: "deadbeef".to_string() }.parse().is_err());
+    }
+}
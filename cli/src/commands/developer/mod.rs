@@ -13,6 +13,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod committee_id;
+pub use committee_id::*;
+
 mod decrypt;
 pub use decrypt::*;
 
@@ -22,6 +25,9 @@ pub use deploy::*;
 mod execute;
 pub use execute::*;
 
+mod inspect_certificate;
+pub use inspect_certificate::*;
+
 mod scan;
 pub use scan::*;
 
@@ -56,12 +62,16 @@ use std::{path::PathBuf, str::FromStr};
 /// Commands to deploy and execute transactions
 #[derive(Debug, Parser)]
 pub enum Developer {
+    /// Derive the canonical ID of a committee from its members.
+    CommitteeId(CommitteeId),
     /// Decrypt a ciphertext.
     Decrypt(Decrypt),
     /// Deploy a program.
     Deploy(Deploy),
     /// Execute a program function.
     Execute(Execute),
+    /// Decode and inspect a serialized batch certificate.
+    InspectCertificate(InspectCertificate),
     /// Scan the node for records.
     Scan(Scan),
     /// Execute the `credits.aleo/transfer_private` function.
@@ -71,9 +81,11 @@ pub enum Developer {
 impl Developer {
     pub fn parse(self) -> Result<String> {
         match self {
+            Self::CommitteeId(committee_id) => committee_id.parse(),
             Self::Decrypt(decrypt) => decrypt.parse(),
             Self::Deploy(deploy) => deploy.parse(),
             Self::Execute(execute) => execute.parse(),
+            Self::InspectCertificate(inspect_certificate) => inspect_certificate.parse(),
             Self::Scan(scan) => scan.parse(),
             Self::TransferPrivate(transfer_private) => transfer_private.parse(),
         }
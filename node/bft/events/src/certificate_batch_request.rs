@@ -0,0 +1,109 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CertificateBatchRequest<N: Network> {
+    pub certificate_ids: IndexSet<Field<N>>,
+}
+
+impl<N: Network> CertificateBatchRequest<N> {
+    /// Initializes a new certificate batch request event.
+    pub fn new(certificate_ids: IndexSet<Field<N>>) -> Self {
+        Self { certificate_ids }
+    }
+}
+
+impl<N: Network> From<IndexSet<Field<N>>> for CertificateBatchRequest<N> {
+    /// Initializes a new certificate batch request event.
+    fn from(certificate_ids: IndexSet<Field<N>>) -> Self {
+        Self::new(certificate_ids)
+    }
+}
+
+impl<N: Network> EventTrait for CertificateBatchRequest<N> {
+    /// Returns the event name.
+    #[inline]
+    fn name(&self) -> Cow<'static, str> {
+        "CertificateBatchRequest".into()
+    }
+}
+
+impl<N: Network> ToBytes for CertificateBatchRequest<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Ensure the number of certificate IDs is within bounds.
+        if self.certificate_ids.len() > N::MAX_CERTIFICATES as usize {
+            return Err(error(format!("Too many certificate IDs ({})", self.certificate_ids.len())));
+        }
+        u16::try_from(self.certificate_ids.len()).map_err(error)?.write_le(&mut writer)?;
+        for certificate_id in &self.certificate_ids {
+            certificate_id.write_le(&mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<N: Network> FromBytes for CertificateBatchRequest<N> {
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        let num_certificates = u16::read_le(&mut reader)?;
+        // Ensure the number of certificate IDs is within bounds.
+        if num_certificates > N::MAX_CERTIFICATES {
+            return Err(error(format!("Too many certificate IDs ({num_certificates})")));
+        }
+        let mut certificate_ids = IndexSet::with_capacity(num_certificates as usize);
+        for _ in 0..num_certificates {
+            certificate_ids.insert(Field::read_le(&mut reader)?);
+        }
+        Ok(Self { certificate_ids })
+    }
+}
+
+#[cfg(test)]
+pub mod prop_tests {
+    use crate::{CertificateBatchRequest, certificate_request::prop_tests::any_field};
+    use snarkvm::console::prelude::{FromBytes, ToBytes};
+
+    use bytes::{Buf, BufMut, BytesMut};
+    use proptest::{
+        collection::vec,
+        prelude::{BoxedStrategy, Strategy},
+    };
+    use test_strategy::proptest;
+
+    type CurrentNetwork = snarkvm::prelude::MainnetV0;
+
+    pub fn any_certificate_batch_request() -> BoxedStrategy<CertificateBatchRequest<CurrentNetwork>> {
+        vec(any_field(), 1..16).prop_map(|ids| CertificateBatchRequest::new(ids.into_iter().collect())).boxed()
+    }
+
+    #[proptest]
+    fn serialize_deserialize(
+        #[strategy(any_certificate_batch_request())] original: CertificateBatchRequest<CurrentNetwork>,
+    ) {
+        let mut buf = BytesMut::default().writer();
+        CertificateBatchRequest::write_le(&original, &mut buf).unwrap();
+
+        let deserialized = CertificateBatchRequest::read_le(buf.into_inner().reader()).unwrap();
+        assert_eq!(original, deserialized);
+    }
+
+    #[test]
+    fn read_rejects_too_many_certificate_ids() {
+        let mut buf = BytesMut::default().writer();
+        (<CurrentNetwork as snarkvm::prelude::Network>::MAX_CERTIFICATES + 1).write_le(&mut buf).unwrap();
+        assert!(CertificateBatchRequest::<CurrentNetwork>::read_le(buf.into_inner().reader()).is_err());
+    }
+}
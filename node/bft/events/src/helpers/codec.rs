@@ -18,6 +18,7 @@ use snarkvm::prelude::{FromBytes, Network, ToBytes};
 
 use bytes::{Buf, BufMut, BytesMut};
 use core::marker::PhantomData;
+use std::sync::Arc;
 use tokio_util::codec::{Decoder, Encoder, LengthDelimitedCodec};
 use tracing::*;
 
@@ -26,9 +27,13 @@ const MAX_HANDSHAKE_SIZE: usize = 1024 * 1024; // 1 MiB
 /// The maximum size of an event that can be transmitted in the network.
 const MAX_EVENT_SIZE: usize = 256 * 1024 * 1024; // 256 MiB
 
+/// An observer of the events encoded or decoded by an `EventCodec`, called with each event and its size in bytes.
+pub type EventObserver<N> = Arc<dyn Fn(&Event<N>, usize) + Send + Sync>;
+
 /// The codec used to decode and encode network `Event`s.
 pub struct EventCodec<N: Network> {
     codec: LengthDelimitedCodec,
+    observer: Option<EventObserver<N>>,
     _phantom: PhantomData<N>,
 }
 
@@ -38,12 +43,20 @@ impl<N: Network> EventCodec<N> {
         codec.codec.set_max_frame_length(MAX_HANDSHAKE_SIZE);
         codec
     }
+
+    /// Sets the observer called with each event encoded or decoded by the codec, and its serialized size,
+    /// so that the size is known without serializing the event again.
+    pub fn with_observer(mut self, observer: EventObserver<N>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
 }
 
 impl<N: Network> Default for EventCodec<N> {
     fn default() -> Self {
         Self {
             codec: LengthDelimitedCodec::builder().max_frame_length(MAX_EVENT_SIZE).little_endian().new_codec(),
+            observer: None,
             _phantom: Default::default(),
         }
     }
@@ -60,6 +73,9 @@ impl<N: Network> Encoder<Event<N>> for EventCodec<N> {
             .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidData, "serialization error"))?;
 
         let serialized_event = dst.split_to(dst.len()).freeze();
+        if let Some(observer) = &self.observer {
+            observer(&event, serialized_event.len());
+        }
 
         self.codec.encode(serialized_event, dst)
     }
@@ -77,9 +93,15 @@ impl<N: Network> Decoder for EventCodec<N> {
         };
 
         // Convert the bytes to an event, or fail if it is not valid.
+        let size = bytes.len();
         let reader = bytes.reader();
         match Event::read_le(reader) {
-            Ok(event) => Ok(Some(event)),
+            Ok(event) => {
+                if let Some(observer) = &self.observer {
+                    observer(&event, size);
+                }
+                Ok(Some(event))
+            }
             Err(error) => {
                 error!("Failed to deserialize an event: {}", error);
                 Err(std::io::ErrorKind::InvalidData.into())
@@ -109,4 +131,19 @@ mod tests {
     fn event_roundtrip(#[strategy(any_event())] event: Event<CurrentNetwork>) {
         assert_roundtrip(event)
     }
+
+    #[proptest]
+    fn observer_reports_event_size(#[strategy(any_event())] event: Event<CurrentNetwork>) {
+        let sizes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sizes_ = sizes.clone();
+        let observer: EventObserver<CurrentNetwork> = Arc::new(move |_, size| sizes_.lock().unwrap().push(size));
+        let mut codec = EventCodec::<CurrentNetwork>::default().with_observer(observer);
+        let mut encoded_event = BytesMut::new();
+
+        // Check that both the encoded and the decoded event are reported with their serialized size.
+        codec.encode(event.clone(), &mut encoded_event).unwrap();
+        codec.decode(&mut encoded_event).unwrap().unwrap();
+        let size = event.to_bytes_le().unwrap().len();
+        assert_eq!(*sizes.lock().unwrap(), vec![size, size]);
+    }
 }
@@ -30,6 +30,9 @@ pub use block_request::BlockRequest;
 mod block_response;
 pub use block_response::{BlockResponse, DataBlocks};
 
+mod certificate_batch_request;
+pub use certificate_batch_request::CertificateBatchRequest;
+
 mod certificate_request;
 pub use certificate_request::CertificateRequest;
 
@@ -108,6 +111,7 @@ pub enum Event<N: Network> {
     ValidatorsRequest(ValidatorsRequest),
     ValidatorsResponse(ValidatorsResponse<N>),
     WorkerPing(WorkerPing<N>),
+    CertificateBatchRequest(CertificateBatchRequest<N>),
 }
 
 impl<N: Network> From<DisconnectReason> for Event<N> {
@@ -118,7 +122,7 @@ impl<N: Network> From<DisconnectReason> for Event<N> {
 
 impl<N: Network> Event<N> {
     /// The version of the event protocol; it can be incremented in order to force users to update.
-    pub const VERSION: u32 = 8;
+    pub const VERSION: u32 = 9;
 
     /// Returns the event name.
     #[inline]
@@ -140,6 +144,7 @@ impl<N: Network> Event<N> {
             Self::ValidatorsRequest(event) => event.name(),
             Self::ValidatorsResponse(event) => event.name(),
             Self::WorkerPing(event) => event.name(),
+            Self::CertificateBatchRequest(event) => event.name(),
         }
     }
 
@@ -163,6 +168,7 @@ impl<N: Network> Event<N> {
             Self::ValidatorsRequest(..) => 13,
             Self::ValidatorsResponse(..) => 14,
             Self::WorkerPing(..) => 15,
+            Self::CertificateBatchRequest(..) => 16,
         }
     }
 }
@@ -188,6 +194,7 @@ impl<N: Network> ToBytes for Event<N> {
             Self::ValidatorsRequest(event) => event.write_le(writer),
             Self::ValidatorsResponse(event) => event.write_le(writer),
             Self::WorkerPing(event) => event.write_le(writer),
+            Self::CertificateBatchRequest(event) => event.write_le(writer),
         }
     }
 }
@@ -215,7 +222,8 @@ impl<N: Network> FromBytes for Event<N> {
             13 => Self::ValidatorsRequest(ValidatorsRequest::read_le(&mut reader)?),
             14 => Self::ValidatorsResponse(ValidatorsResponse::read_le(&mut reader)?),
             15 => Self::WorkerPing(WorkerPing::read_le(&mut reader)?),
-            16.. => return Err(error(format!("Unknown event ID {id}"))),
+            16 => Self::CertificateBatchRequest(CertificateBatchRequest::read_le(&mut reader)?),
+            17.. => return Err(error(format!("Unknown event ID {id}"))),
         };
 
         // Ensure that there are no "dangling" bytes.
@@ -255,6 +263,7 @@ pub mod prop_tests {
         batch_certified::prop_tests::any_batch_certified,
         batch_propose::prop_tests::any_batch_propose,
         batch_signature::prop_tests::any_batch_signature,
+        certificate_batch_request::prop_tests::any_certificate_batch_request,
         certificate_request::prop_tests::any_certificate_request,
         certificate_response::prop_tests::any_certificate_response,
         challenge_request::prop_tests::any_challenge_request,
@@ -311,6 +320,7 @@ pub mod prop_tests {
             any_batch_certified().prop_map(Event::BatchCertified),
             any_batch_propose().prop_map(Event::BatchPropose),
             any_batch_signature().prop_map(Event::BatchSignature),
+            any_certificate_batch_request().prop_map(Event::CertificateBatchRequest),
             any_certificate_request().prop_map(Event::CertificateRequest),
             any_certificate_response().prop_map(Event::CertificateResponse),
             any_challenge_request().prop_map(Event::ChallengeRequest),
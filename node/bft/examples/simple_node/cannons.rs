@@ -0,0 +1,492 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The cannons, firing *fake* unconfirmed transmissions at the node.
+
+use super::{Args, CANNONS_PAUSED, CurrentNetwork, NUM_DROPPED, NUM_FIRED, NUM_STUCK, sleep_scaled, spawn_task};
+
+use snarkos_node_bft::{Primary, helpers::PrimarySender};
+use snarkvm::{
+    ledger::{
+        block::Transaction,
+        narwhal::{Data, Transmission},
+        puzzle::{Solution, SolutionID},
+    },
+    prelude::{Field, Network, Uniform},
+};
+
+use ::bytes::Bytes;
+use anyhow::Result;
+use rand::{Rng, SeedableRng};
+use std::{
+    path::PathBuf,
+    str::FromStr,
+    sync::{
+        OnceLock,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+use tokio::sync::{mpsc, oneshot};
+
+/// The total number of transmission bytes fired by this node.
+static NUM_FIRED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// The number of transactions sent by each emulated client (see `--clients`).
+static NUM_SENT_BY_CLIENT: OnceLock<Vec<AtomicU64>> = OnceLock::new();
+
+/// The total number of transmissions re-fired by the mirror (see `--mirror`).
+static NUM_MIRRORED: AtomicU64 = AtomicU64::new(0);
+
+/// The time (in milliseconds) to await the primary's callback for a transmission, before counting it as stuck.
+static CALLBACK_TIMEOUT_MS: AtomicU64 = AtomicU64::new(60_000);
+
+/// Sends the given transmission to the primary, awaits its callback, and returns `true` if the primary accepted it.
+/// If the primary drops the callback (e.g. when shedding load), the send is retried up to `retries` times.
+async fn fire_with_retries<I: Copy, T: Clone>(
+    sender: &mpsc::Sender<(I, T, oneshot::Sender<Result<()>>)>,
+    id: I,
+    transmission: T,
+    retries: u32,
+    kind: &str,
+) -> bool {
+    for attempt in 0..=retries {
+        // Initialize a callback sender and receiver.
+        let (callback, callback_receiver) = oneshot::channel();
+        // Send the transmission.
+        if let Err(e) = sender.send((id, transmission.clone(), callback)).await {
+            error!("Failed to send unconfirmed {kind}: {e}");
+            return false;
+        }
+        let timeout = std::time::Duration::from_millis(CALLBACK_TIMEOUT_MS.load(Ordering::Relaxed));
+        let Ok(callback) = tokio::time::timeout(timeout, callback_receiver).await else {
+            let num_stuck = NUM_STUCK.fetch_add(1, Ordering::Relaxed) + 1;
+            warn!("The primary did not answer an unconfirmed {kind} callback in {timeout:?} ({num_stuck} total)");
+            return false;
+        };
+        match callback {
+            Ok(Ok(())) => return true,
+            Ok(Err(e)) => {
+                trace!("The primary rejected an unconfirmed {kind}: {e}");
+                return false;
+            }
+            Err(_) => {
+                let num_dropped = NUM_DROPPED.fetch_add(1, Ordering::Relaxed) + 1;
+                debug!(
+                    "The primary dropped the callback of an unconfirmed {kind} (attempt {attempt}, {num_dropped} total)"
+                );
+            }
+        }
+    }
+    false
+}
+
+/// Records the given number of fired bytes, and returns `true` if the byte budget (if any) has been reached.
+fn record_fired_bytes(num_bytes: u64, byte_budget: Option<u64>, start: std::time::Instant) -> bool {
+    let total = NUM_FIRED_BYTES.fetch_add(num_bytes, Ordering::Relaxed) + num_bytes;
+    match byte_budget {
+        Some(budget) if total >= budget => {
+            // Log the achieved throughput once, from the cannon that exhausted the budget.
+            if total - num_bytes < budget {
+                let elapsed = start.elapsed().as_secs_f64().max(f64::EPSILON);
+                info!(
+                    "Reached the byte budget of {budget} bytes - fired {total} bytes in {elapsed:.2}s ({:.0} bytes/sec)",
+                    total as f64 / elapsed
+                );
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+/// The interval (in milliseconds) read from the fire control file, or `0` if it was not set.
+static FIRE_CONTROL_INTERVAL_MS: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the interval to fire transmissions at, preferring the interval read from the fire control file.
+fn fire_interval_ms(default_interval_ms: u64) -> u64 {
+    match FIRE_CONTROL_INTERVAL_MS.load(Ordering::Relaxed) {
+        0 => default_interval_ms,
+        interval_ms => interval_ms,
+    }
+}
+
+/// Waits while the cannons are paused (see `--max-memory-mb`).
+async fn wait_while_paused() {
+    while CANNONS_PAUSED.load(Ordering::Relaxed) {
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+}
+
+/// Periodically reads the fire interval (in milliseconds) from the given control file.
+/// If the file cannot be read or parsed, the previous interval is kept, and a warning is logged.
+fn watch_fire_control_file(path: PathBuf) {
+    spawn_task("fire control file watcher", async move {
+        let mut previous_contents = None;
+        loop {
+            let contents = std::fs::read_to_string(&path).map(|contents| contents.trim().to_string());
+            // Only process the file when its contents change, to avoid repeating the warnings.
+            let key = contents.as_ref().map_err(|e| e.kind()).cloned();
+            if previous_contents.as_ref() != Some(&key) {
+                match contents.map_err(|e| e.to_string()).and_then(|c| u64::from_str(&c).map_err(|e| e.to_string())) {
+                    Ok(interval_ms) if interval_ms > 0 => {
+                        info!("Firing transmissions every {interval_ms} ms (from '{}')", path.display());
+                        FIRE_CONTROL_INTERVAL_MS.store(interval_ms, Ordering::Relaxed);
+                    }
+                    Ok(_) => warn!("Ignoring the zero interval in '{}'", path.display()),
+                    Err(e) => warn!("Keeping the previous fire interval, as '{}' is invalid - {e}", path.display()),
+                }
+                previous_contents = Some(key);
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        }
+    });
+}
+
+/// Waits until the given number of peers are connected (if set), and then for the warm-up period (if set).
+async fn warm_up_cannons(primary: &Primary<CurrentNetwork>, min_peers: Option<usize>, warmup_secs: Option<u64>) {
+    if let Some(min_peers) = min_peers {
+        while primary.gateway().number_of_connected_peers() < min_peers {
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        }
+        info!("Connected to {min_peers} peers, the cannons may start");
+    }
+    if let Some(warmup_secs) = warmup_secs {
+        info!("Warming up for {warmup_secs} seconds before starting the cannons...");
+        sleep_scaled(std::time::Duration::from_secs(warmup_secs)).await;
+    }
+}
+
+/// Starts the cannons as configured in the given arguments, once the warm-up (if any) is over.
+pub fn spawn_cannons(args: &Args, primary: &Primary<CurrentNetwork>, sender: PrimarySender<CurrentNetwork>) {
+    // Set the time to await the primary's callback for each transmission.
+    CALLBACK_TIMEOUT_MS.store(args.callback_timeout_ms, Ordering::Relaxed);
+    // If a fire control file was provided, read the fire interval from it.
+    if let Some(path) = args.fire_control_file.clone() {
+        watch_fire_control_file(path);
+    }
+    let (args, primary) = (args.clone(), primary.clone());
+    spawn_task("cannons", async move {
+        warm_up_cannons(&primary, args.min_peers_to_start, args.fire_warmup_secs).await;
+        start_cannons(&args, &sender);
+        start_clients(&args, &sender);
+        if args.mirror {
+            start_mirror(&primary, &sender, args.mirror_cap, args.fire_retries);
+        }
+    });
+}
+
+/// **For stress testing only.** Mirrors the transmissions the node receives from its peers: once per second,
+/// the transmissions of the peers' new certificates are re-submitted to the node's own primary under fresh IDs,
+/// until `cap` transmissions were re-fired in total. This feeds the received load back into the ingest path of
+/// the node, to find propagation bottlenecks; the cap bounds the amplification.
+fn start_mirror(primary: &Primary<CurrentNetwork>, sender: &PrimarySender<CurrentNetwork>, cap: u64, retries: u32) {
+    warn!("Mirroring the received transmissions (up to {cap}) - this mode is for stress testing only");
+    let (primary, sender) = (primary.clone(), sender.clone());
+    spawn_task("mirror", async move {
+        let address = primary.gateway().account().address();
+        let mut rng = rand_chacha::ChaChaRng::from_entropy();
+        let mut last_round = primary.current_round();
+        loop {
+            sleep_scaled(std::time::Duration::from_secs(1)).await;
+            let current_round = primary.current_round();
+            for round in last_round + 1..=current_round {
+                // Re-fire the transmissions of the peers' certificates in the round.
+                for certificate in primary.storage().get_certificates_for_round(round) {
+                    if certificate.author() == address {
+                        continue;
+                    }
+                    for transmission_id in certificate.transmission_ids() {
+                        if NUM_MIRRORED.load(Ordering::Relaxed) >= cap {
+                            info!("The mirror reached its cap of {cap} re-fired transmissions");
+                            return;
+                        }
+                        let Some(transmission) = primary.storage().get_transmission(*transmission_id) else {
+                            continue;
+                        };
+                        if mirror_transmission(&sender, transmission, retries, &mut rng).await {
+                            NUM_MIRRORED.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                }
+            }
+            last_round = last_round.max(current_round);
+        }
+    });
+}
+
+/// Re-fires the given transmission at the node under a fresh ID, and returns `true` if the primary accepted it.
+/// Note: The node ignores the transmissions it already holds, so re-firing a transmission under its own ID
+/// would neither be ingested nor propagated again.
+async fn mirror_transmission(
+    sender: &PrimarySender<CurrentNetwork>,
+    transmission: Transmission<CurrentNetwork>,
+    retries: u32,
+    rng: &mut impl Rng,
+) -> bool {
+    match transmission {
+        Transmission::Solution(solution) => {
+            let solution_id = rng.gen::<u64>().into();
+            fire_with_retries(&sender.tx_unconfirmed_solution, solution_id, solution, retries, "solution").await
+        }
+        Transmission::Transaction(transaction) => {
+            let id = Field::<CurrentNetwork>::rand(rng).into();
+            fire_with_retries(&sender.tx_unconfirmed_transaction, id, transaction, retries, "transaction").await
+        }
+        Transmission::Ratification => false,
+    }
+}
+
+/// Starts the solution and transaction cannons, as configured in the given arguments.
+fn start_cannons(args: &Args, sender: &PrimarySender<CurrentNetwork>) {
+    // The default interval to fire transmissions at.
+    const DEFAULT_INTERVAL_MS: u64 = 450; // ms
+
+    // Fire unconfirmed solutions.
+    match (args.fire_transmissions, args.fire_solutions) {
+        // Note: We allow the user to overload the solutions rate, even when the 'fire-transmissions' flag is enabled.
+        (Some(rate), _) | (_, Some(rate)) => {
+            fire_unconfirmed_solutions(
+                sender,
+                args.id,
+                rate.unwrap_or(DEFAULT_INTERVAL_MS),
+                args.fire_byte_budget,
+                args.fire_retries,
+                args.duplicate_rate,
+            );
+        }
+        _ => (),
+    };
+
+    // Fire unconfirmed transactions.
+    match (args.fire_transmissions, args.fire_transactions) {
+        // Note: We allow the user to overload the transactions rate, even when the 'fire-transmissions' flag is enabled.
+        (Some(rate), _) | (_, Some(rate)) => {
+            fire_unconfirmed_transactions(
+                sender,
+                args.id,
+                rate.unwrap_or(DEFAULT_INTERVAL_MS),
+                args.fire_byte_budget,
+                args.fire_retries,
+                args.duplicate_rate,
+            );
+        }
+        _ => (),
+    };
+}
+
+/// Starts the emulated clients, each firing transactions from its own ID space and at its own interval.
+fn start_clients(args: &Args, sender: &PrimarySender<CurrentNetwork>) {
+    // The default interval for the clients to fire transactions at.
+    const DEFAULT_INTERVAL_MS: u64 = 450; // ms
+
+    let Some(num_clients) = args.clients else {
+        return;
+    };
+    let counts = NUM_SENT_BY_CLIENT.get_or_init(|| (0..num_clients).map(|_| AtomicU64::new(0)).collect());
+    for (client, num_sent) in counts.iter().enumerate() {
+        // Determine the interval of the client, cycling through the given intervals.
+        let interval_ms = if args.client_interval_ms.is_empty() {
+            DEFAULT_INTERVAL_MS
+        } else {
+            args.client_interval_ms[client % args.client_interval_ms.len()]
+        };
+        // Derive a seed distinct from the seeds of the nodes' cannons, and of the other nodes' clients.
+        let seed = (1 << 63) | ((args.id as u64) << 32) | client as u64;
+        info!("Starting client {client}, firing a transaction every {interval_ms} ms");
+        let (byte_budget, retries) = (args.fire_byte_budget, args.fire_retries);
+        fire_transactions_from(sender, seed, interval_ms, byte_budget, retries, 0.0, Some(num_sent));
+    }
+}
+
+/// Logs the number of transactions sent by each emulated client, if any.
+pub fn log_client_summary() {
+    let Some(counts) = NUM_SENT_BY_CLIENT.get() else {
+        return;
+    };
+    let counts = counts.iter().map(|num_sent| num_sent.load(Ordering::Relaxed)).collect::<Vec<_>>();
+    info!("Transactions sent by each client: {counts:?} (total {})", counts.iter().sum::<u64>());
+}
+
+/// Fires *fake* unconfirmed solutions at the node.
+/// Each solution is a duplicate shared by all nodes with probability `duplicate_rate`, and unique otherwise.
+pub fn fire_unconfirmed_solutions(
+    sender: &PrimarySender<CurrentNetwork>,
+    node_id: u16,
+    interval_ms: u64,
+    byte_budget: Option<u64>,
+    retries: u32,
+    duplicate_rate: f64,
+) {
+    let tx_unconfirmed_solution = sender.tx_unconfirmed_solution.clone();
+    spawn_task("solution cannon", async move {
+        // This RNG samples the *same* fake solutions for all nodes.
+        let mut shared_rng = rand_chacha::ChaChaRng::seed_from_u64(123456789);
+        // This RNG samples *different* fake solutions for each node.
+        let mut unique_rng = rand_chacha::ChaChaRng::seed_from_u64(node_id as u64);
+
+        // A closure to generate a solution ID and solution.
+        fn sample(mut rng: impl Rng) -> (SolutionID<CurrentNetwork>, Data<Solution<CurrentNetwork>>) {
+            // Sample a random fake solution ID.
+            let solution_id = rng.gen::<u64>().into();
+            // Sample random fake solution bytes.
+            let solution = Data::Buffer(Bytes::from((0..1024).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+            // Return the ID and solution.
+            (solution_id, solution)
+        }
+
+        // Track the start time, to compute the byte throughput.
+        let start = std::time::Instant::now();
+
+        loop {
+            // Wait while the cannons are paused.
+            wait_while_paused().await;
+            // Sample a random fake solution ID and solution.
+            // Note: The choice is drawn from the unique RNG, so all nodes still sample the shared ones in order.
+            let (solution_id, solution) =
+                if unique_rng.gen_bool(duplicate_rate) { sample(&mut shared_rng) } else { sample(&mut unique_rng) };
+            // Determine the number of bytes being fired.
+            let num_bytes = match &solution {
+                Data::Buffer(bytes) => bytes.len() as u64,
+                _ => 0,
+            };
+            // Send the fake solution.
+            fire_with_retries(&tx_unconfirmed_solution, solution_id, solution, retries, "solution").await;
+            // Increment the counter.
+            NUM_FIRED.fetch_add(1, Ordering::Relaxed);
+            // Stop firing once the byte budget is reached.
+            if record_fired_bytes(num_bytes, byte_budget, start) {
+                break;
+            }
+            // Sleep briefly.
+            sleep_scaled(std::time::Duration::from_millis(fire_interval_ms(interval_ms))).await;
+        }
+    });
+}
+
+/// Fires *fake* unconfirmed transactions at the node.
+/// Each transaction is a duplicate shared by all nodes with probability `duplicate_rate`, and unique otherwise.
+pub fn fire_unconfirmed_transactions(
+    sender: &PrimarySender<CurrentNetwork>,
+    node_id: u16,
+    interval_ms: u64,
+    byte_budget: Option<u64>,
+    retries: u32,
+    duplicate_rate: f64,
+) {
+    fire_transactions_from(sender, node_id as u64, interval_ms, byte_budget, retries, duplicate_rate, None)
+}
+
+/// Fires *fake* unconfirmed transactions at the node, sampling the unique transactions from the given seed.
+/// If a counter is given (i.e. for an emulated client), the counter is incremented for each transaction sent.
+fn fire_transactions_from(
+    sender: &PrimarySender<CurrentNetwork>,
+    unique_seed: u64,
+    interval_ms: u64,
+    byte_budget: Option<u64>,
+    retries: u32,
+    duplicate_rate: f64,
+    num_sent: Option<&'static AtomicU64>,
+) {
+    let tx_unconfirmed_transaction = sender.tx_unconfirmed_transaction.clone();
+    spawn_task("transaction cannon", async move {
+        // This RNG samples the *same* fake transactions for all nodes.
+        let mut shared_rng = rand_chacha::ChaChaRng::seed_from_u64(123456789);
+        // This RNG samples *different* fake transactions for each node (or client).
+        let mut unique_rng = rand_chacha::ChaChaRng::seed_from_u64(unique_seed);
+
+        // A closure to generate an ID and transaction.
+        fn sample(
+            mut rng: impl Rng,
+        ) -> (<CurrentNetwork as Network>::TransactionID, Data<Transaction<CurrentNetwork>>) {
+            // Sample a random fake transaction ID.
+            let id = Field::<CurrentNetwork>::rand(&mut rng).into();
+            // Sample random fake transaction bytes.
+            let transaction = Data::Buffer(Bytes::from((0..1024).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+            // Return the ID and transaction.
+            (id, transaction)
+        }
+
+        // Track the start time, to compute the byte throughput.
+        let start = std::time::Instant::now();
+
+        loop {
+            // Wait while the cannons are paused.
+            wait_while_paused().await;
+            // Sample a random fake transaction ID and transaction.
+            // Note: The choice is drawn from the unique RNG, so all nodes still sample the shared ones in order.
+            let (id, transaction) =
+                if unique_rng.gen_bool(duplicate_rate) { sample(&mut shared_rng) } else { sample(&mut unique_rng) };
+            // Determine the number of bytes being fired.
+            let num_bytes = match &transaction {
+                Data::Buffer(bytes) => bytes.len() as u64,
+                _ => 0,
+            };
+            // Send the fake transaction.
+            fire_with_retries(&tx_unconfirmed_transaction, id, transaction, retries, "transaction").await;
+            // Increment the counters.
+            NUM_FIRED.fetch_add(1, Ordering::Relaxed);
+            if let Some(num_sent) = num_sent {
+                num_sent.fetch_add(1, Ordering::Relaxed);
+            }
+            // Stop firing once the byte budget is reached.
+            if record_fired_bytes(num_bytes, byte_budget, start) {
+                break;
+            }
+            // Sleep briefly.
+            sleep_scaled(std::time::Duration::from_millis(fire_interval_ms(interval_ms))).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use snarkos_node_bft::helpers::init_primary_channels;
+    use snarkvm::ledger::narwhal::TransmissionID;
+
+    #[tokio::test]
+    async fn mirror_transmission_refires_under_fresh_id() {
+        let (sender, mut receiver) = init_primary_channels::<CurrentNetwork>();
+        let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+
+        // Sample a transaction the node already holds.
+        let transaction_id = Field::<CurrentNetwork>::rand(&mut rng).into();
+        let transaction = Data::Buffer(Bytes::from((0..1024).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+        let checksum = transaction.to_checksum::<CurrentNetwork>().unwrap();
+        let held_id = TransmissionID::Transaction(transaction_id, checksum);
+
+        // Accept the mirrored transaction, as the primary does for a transmission it does not hold yet.
+        let primary = tokio::spawn(async move {
+            let (id, transaction, callback) = receiver.rx_unconfirmed_transaction.recv().await.unwrap();
+            callback.send(Ok(())).unwrap();
+            TransmissionID::Transaction(id, transaction.to_checksum::<CurrentNetwork>().unwrap())
+        });
+        let transmission = Transmission::Transaction(transaction);
+        assert!(mirror_transmission(&sender, transmission, 0, &mut rng).await);
+
+        // Ensure the mirrored transaction is a new transmission, which the node ingests and propagates again.
+        let mirrored_id = primary.await.unwrap();
+        assert_ne!(mirrored_id, held_id);
+        assert_eq!(mirrored_id.checksum(), held_id.checksum());
+    }
+
+    #[tokio::test]
+    async fn mirror_transmission_skips_ratifications() {
+        let (sender, _receiver) = init_primary_channels::<CurrentNetwork>();
+        let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+        assert!(!mirror_transmission(&sender, Transmission::Ratification, 0, &mut rng).await);
+    }
+}
@@ -0,0 +1,2319 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[macro_use]
+extern crate tracing;
+
+#[cfg(feature = "cannons")]
+mod cannons;
+#[cfg(feature = "rest")]
+mod rest;
+
+use snarkos_account::Account;
+use snarkos_node_bft::{
+    BFT,
+    MEMORY_POOL_PORT,
+    Primary,
+    helpers::{
+        ConsensusReceiver,
+        PrimarySender,
+        ProposalCache,
+        Storage,
+        init_consensus_channels,
+        init_primary_channels,
+        proposal_cache_path,
+    },
+};
+use snarkos_node_bft_ledger_service::{CommitteeMembers, EpochLedgerService, LedgerService, TranslucentLedgerService};
+use snarkos_node_bft_storage_service::BFTMemoryService;
+use snarkvm::{
+    console::{account::PrivateKey, algorithms::BHP256, types::Address},
+    ledger::{
+        Block,
+        Ledger,
+        committee::{Committee, MIN_VALIDATOR_STAKE},
+        narwhal::{BatchHeader, Data, Transmission, TransmissionID},
+        store::{ConsensusStore, helpers::memory::ConsensusMemory},
+    },
+    prelude::{Hash, Network, VM},
+    utilities::{FromBytes, TestRng, ToBits, ToBytes, to_bytes_le},
+};
+
+use anyhow::{Error, Result, anyhow, bail, ensure};
+use clap::{ArgAction, Command, CommandFactory, FromArgMatches, Parser, ValueEnum, parser::ValueSource};
+use indexmap::IndexMap;
+use rand::{CryptoRng, Rng, SeedableRng};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{HashMap, HashSet},
+    ffi::OsString,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{
+        Arc,
+        Mutex,
+        OnceLock,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+};
+use tracing_subscriber::{
+    layer::{Layer, SubscriberExt},
+    util::SubscriberInitExt,
+};
+
+type CurrentNetwork = snarkvm::prelude::MainnetV0;
+
+/**************************************************************************************************/
+
+/// The rotation policy of the log file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LogRotation {
+    /// The log file is never rotated.
+    Never,
+    /// The log file is rotated every hour.
+    Hourly,
+    /// The log file is rotated every day.
+    Daily,
+    /// The log file is rotated once it exceeds the given size (in megabytes).
+    Size(u64),
+}
+
+impl FromStr for LogRotation {
+    type Err = String;
+
+    /// Parses a log rotation policy of the form `never`, `hourly`, `daily`, or `size:<MB>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "never" => Ok(Self::Never),
+            "hourly" => Ok(Self::Hourly),
+            "daily" => Ok(Self::Daily),
+            _ => match s.strip_prefix("size:").map(u64::from_str) {
+                Some(Ok(megabytes)) if megabytes > 0 => Ok(Self::Size(megabytes)),
+                _ => Err(format!("Invalid log rotation '{s}' (expected 'never', 'hourly', 'daily', or 'size:<MB>')")),
+            },
+        }
+    }
+}
+
+/// A log file that is rotated according to a `LogRotation` policy.
+/// On rotation, the current file is renamed with the rotation timestamp as a suffix, and a new file is started.
+struct RotatingLogFile {
+    /// The path to the log file.
+    path: PathBuf,
+    /// The rotation policy.
+    rotation: LogRotation,
+    /// The open file, the number of bytes written to it, and the period it was opened in.
+    state: Mutex<(std::fs::File, u64, u64)>,
+}
+
+impl RotatingLogFile {
+    /// Opens (or creates) the log file at the given path.
+    fn new(path: PathBuf, rotation: LogRotation) -> std::io::Result<Self> {
+        let file = std::fs::File::options().append(true).create(true).open(&path)?;
+        let num_bytes = file.metadata()?.len();
+        let period = Self::period(rotation);
+        Ok(Self { path, rotation, state: Mutex::new((file, num_bytes, period)) })
+    }
+
+    /// Returns the current rotation period, for time-based rotation policies.
+    fn period(rotation: LogRotation) -> u64 {
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs();
+        match rotation {
+            LogRotation::Hourly => now / 3600,
+            LogRotation::Daily => now / 86400,
+            LogRotation::Never | LogRotation::Size(_) => 0,
+        }
+    }
+}
+
+impl std::io::Write for &RotatingLogFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let period = RotatingLogFile::period(self.rotation);
+        // Determine if the log file needs to be rotated.
+        let should_rotate = match self.rotation {
+            LogRotation::Never => false,
+            LogRotation::Hourly | LogRotation::Daily => period != state.2,
+            LogRotation::Size(megabytes) => state.1 + buf.len() as u64 > megabytes * 1024 * 1024,
+        };
+        if should_rotate && state.1 > 0 {
+            state.0.flush()?;
+            let timestamp = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+            let mut rotated = self.path.clone().into_os_string();
+            rotated.push(format!(".{}", timestamp.as_millis()));
+            std::fs::rename(&self.path, rotated)?;
+            *state = (std::fs::File::options().append(true).create(true).open(&self.path)?, 0, period);
+        }
+        let num_bytes = state.0.write(buf)?;
+        state.1 += num_bytes as u64;
+        Ok(num_bytes)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).0.flush()
+    }
+}
+
+/// Initializes the logger.
+/// Note: If a log file is provided, the logs are also written to it, and it is rotated according to the given policy.
+pub fn initialize_logger(verbosity: u8, logfile: Option<(PathBuf, LogRotation)>) {
+    match verbosity {
+        0 => std::env::set_var("RUST_LOG", "info"),
+        1 => std::env::set_var("RUST_LOG", "debug"),
+        2..=4 => std::env::set_var("RUST_LOG", "trace"),
+        _ => std::env::set_var("RUST_LOG", "info"),
+    };
+
+    // Filter out undesirable logs. (unfortunately EnvFilter cannot be cloned)
+    let [filter, file_filter] = std::array::from_fn(|_| {
+        let filter = tracing_subscriber::EnvFilter::from_default_env()
+            .add_directive("mio=off".parse().unwrap())
+            .add_directive("tokio_util=off".parse().unwrap())
+            .add_directive("hyper=off".parse().unwrap())
+            .add_directive("reqwest=off".parse().unwrap())
+            .add_directive("want=off".parse().unwrap())
+            .add_directive("warp=off".parse().unwrap());
+
+        let filter = if verbosity > 3 {
+            filter.add_directive("snarkos_node_bft::gateway=trace".parse().unwrap())
+        } else {
+            filter.add_directive("snarkos_node_bft::gateway=off".parse().unwrap())
+        };
+
+        if verbosity > 4 {
+            filter.add_directive("snarkos_node_tcp=trace".parse().unwrap())
+        } else {
+            filter.add_directive("snarkos_node_tcp=off".parse().unwrap())
+        }
+    });
+
+    // Initialize the log file layer, if a log file is provided.
+    let file_layer = logfile.and_then(|(path, rotation)| match RotatingLogFile::new(path.clone(), rotation) {
+        Ok(logfile) => Some(
+            tracing_subscriber::fmt::Layer::default()
+                .with_ansi(false)
+                .with_writer(Arc::new(logfile))
+                .with_target(verbosity > 2)
+                .with_filter(file_filter),
+        ),
+        Err(e) => {
+            eprintln!("Failed to open the log file '{}' - {e}", path.display());
+            None
+        }
+    });
+
+    // Initialize tracing.
+    let _ = tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::Layer::default().with_target(verbosity > 2).with_filter(filter))
+        .with(file_layer)
+        .try_init();
+}
+
+/**************************************************************************************************/
+
+/// Starts the BFT instance.
+async fn start_bft(
+    args: &Args,
+    peers: HashMap<u16, SocketAddr>,
+) -> Result<(BFT<CurrentNetwork>, PrimarySender<CurrentNetwork>)> {
+    let (node_id, num_nodes) = (args.id, args.num_nodes);
+    // Initialize the primary channels.
+    let (sender, receiver) = init_primary_channels();
+    // Initialize the components.
+    let (committee, account) = initialize_components(args, &peers)?;
+    // Initialize the translucent ledger service.
+    let ledger = create_ledger(&account, num_nodes, committee, node_id, args.epoch_length)?;
+    // Initialize the storage.
+    let storage = Storage::new(
+        ledger.clone(),
+        Arc::new(BFTMemoryService::new()),
+        BatchHeader::<CurrentNetwork>::MAX_GC_ROUNDS as u64,
+    );
+    // If set, remember the stored transmission IDs for deduplication beyond the GC window.
+    if let Some(rounds) = args.dedup_window_rounds {
+        storage.set_dedup_window_rounds(rounds);
+    }
+    // Initialize the gateway IP and dev mode.
+    let (ip, dev) = match peers.get(&node_id) {
+        Some(ip) => (Some(*ip), None),
+        None => (None, Some(node_id)),
+    };
+    // Restore the newest valid checkpoint in the storage path (if any), for the primary to load.
+    let verify_with = args.verify_on_load.then_some(&*ledger);
+    restore_checkpoint(args.storage_path.as_deref(), node_id, account.address(), dev, verify_with)?;
+    // Initialize the trusted validators.
+    let trusted_validators = trusted_validators(node_id, num_nodes, peers);
+    // Initialize the consensus channels.
+    let (consensus_sender, consensus_receiver) = init_consensus_channels::<CurrentNetwork>();
+    // Initialize the consensus receiver handler.
+    consensus_handler(consensus_receiver);
+    // Initialize the BFT instance.
+    let mut bft = BFT::<CurrentNetwork>::new(account, storage, ledger, ip, &trusted_validators, dev)?;
+    // If a proxy is provided, route the outbound connections through it.
+    if let Some(proxy) = args.proxy {
+        bft.primary().gateway().set_proxy(proxy)?;
+    }
+    // If a handshake timeout is provided, set it in the gateway.
+    if let Some(timeout_ms) = args.handshake_timeout_ms {
+        bft.primary().gateway().set_handshake_timeout_ms(timeout_ms);
+    }
+    // Set whether the gateway logs each peer connection and disconnection.
+    bft.primary().gateway().set_log_connection_events(args.log_connection_events);
+    bft.primary().gateway().set_relay_transmissions(!args.no_relay);
+    bft.primary()
+        .gateway()
+        .set_connection_failure_thresholds(args.warn_after_failures, args.error_after_failures.unwrap_or(u32::MAX))?;
+    // If a gateway log is provided, record every event sent and received by the gateway to it.
+    if let Some(path) = &args.gateway_log {
+        bft.primary().gateway().set_message_log(path)?;
+    }
+    // If an inbound drop rate is provided, set it in the gateway.
+    if let Some(rate) = args.drop_inbound_rate {
+        bft.primary().gateway().set_inbound_drop_rate(rate, u64::from(args.id))?;
+    }
+    // If a number of primary workers is provided, set it in the primary.
+    if let Some(num_workers) = args.primary_workers {
+        bft.primary().set_num_transmission_workers(num_workers)?;
+    }
+    // Set whether the primary eagerly materializes the received transmissions.
+    bft.primary().set_eager_materialization(args.transmission_materialization == Materialization::Eager);
+    // If a BFT warm-up is provided, delay the primary's first proposal after the BFT is initialized.
+    if let Some(warmup_ms) = args.bft_warmup_ms {
+        bft.primary().set_first_proposal_delay_in_ms(warmup_ms);
+    }
+    // Run the BFT instance.
+    bft.run(Some(consensus_sender), sender.clone(), receiver).await?;
+    // Return the BFT instance.
+    Ok((bft, sender))
+}
+
+/// Starts the primary instance.
+async fn start_primary(
+    args: &Args,
+    peers: HashMap<u16, SocketAddr>,
+) -> Result<(Primary<CurrentNetwork>, PrimarySender<CurrentNetwork>)> {
+    let (node_id, num_nodes) = (args.id, args.num_nodes);
+    // Initialize the primary channels.
+    let (sender, receiver) = init_primary_channels();
+    // Initialize the components.
+    let (committee, account) = initialize_components(args, &peers)?;
+    // Initialize the translucent ledger service.
+    let ledger = create_ledger(&account, num_nodes, committee, node_id, args.epoch_length)?;
+    // Initialize the storage.
+    let storage = Storage::new(
+        ledger.clone(),
+        Arc::new(BFTMemoryService::new()),
+        BatchHeader::<CurrentNetwork>::MAX_GC_ROUNDS as u64,
+    );
+    // If set, remember the stored transmission IDs for deduplication beyond the GC window.
+    if let Some(rounds) = args.dedup_window_rounds {
+        storage.set_dedup_window_rounds(rounds);
+    }
+    // Initialize the gateway IP and dev mode.
+    let (ip, dev) = match peers.get(&node_id) {
+        Some(ip) => (Some(*ip), None),
+        None => (None, Some(node_id)),
+    };
+    // Restore the newest valid checkpoint in the storage path (if any), for the primary to load.
+    let verify_with = args.verify_on_load.then_some(&*ledger);
+    restore_checkpoint(args.storage_path.as_deref(), node_id, account.address(), dev, verify_with)?;
+    // Initialize the trusted validators.
+    let trusted_validators = trusted_validators(node_id, num_nodes, peers);
+    // Initialize the primary instance.
+    let mut primary = Primary::<CurrentNetwork>::new(account, storage, ledger, ip, &trusted_validators, dev)?;
+    // If a proxy is provided, route the outbound connections through it.
+    if let Some(proxy) = args.proxy {
+        primary.gateway().set_proxy(proxy)?;
+    }
+    // If a handshake timeout is provided, set it in the gateway.
+    if let Some(timeout_ms) = args.handshake_timeout_ms {
+        primary.gateway().set_handshake_timeout_ms(timeout_ms);
+    }
+    // Set whether the gateway logs each peer connection and disconnection.
+    primary.gateway().set_log_connection_events(args.log_connection_events);
+    primary.gateway().set_relay_transmissions(!args.no_relay);
+    primary
+        .gateway()
+        .set_connection_failure_thresholds(args.warn_after_failures, args.error_after_failures.unwrap_or(u32::MAX))?;
+    // If a gateway log is provided, record every event sent and received by the gateway to it.
+    if let Some(path) = &args.gateway_log {
+        primary.gateway().set_message_log(path)?;
+    }
+    // If an inbound drop rate is provided, set it in the gateway.
+    if let Some(rate) = args.drop_inbound_rate {
+        primary.gateway().set_inbound_drop_rate(rate, u64::from(args.id))?;
+    }
+    // If a number of primary workers is provided, set it in the primary.
+    if let Some(num_workers) = args.primary_workers {
+        primary.set_num_transmission_workers(num_workers)?;
+    }
+    // Set whether the primary eagerly materializes the received transmissions.
+    primary.set_eager_materialization(args.transmission_materialization == Materialization::Eager);
+    // Run the primary instance.
+    primary.run(None, sender.clone(), receiver).await?;
+    // Return the primary instance.
+    Ok((primary, sender))
+}
+
+/// Initialize the translucent ledger service.
+/// If an epoch length is provided, the committee is rotated every `epoch_length` rounds.
+fn create_ledger(
+    account: &Account<CurrentNetwork>,
+    num_nodes: u16,
+    committee: Committee<snarkvm::prelude::MainnetV0>,
+    node_id: u16,
+    epoch_length: Option<u64>,
+) -> Result<Arc<dyn LedgerService<CurrentNetwork>>> {
+    let gen_key = account.private_key();
+    let public_balance_per_validator =
+        (CurrentNetwork::STARTING_SUPPLY - (num_nodes as u64) * MIN_VALIDATOR_STAKE) / (num_nodes as u64);
+    let mut balances = IndexMap::<Address<CurrentNetwork>, u64>::new();
+    for address in committee.members().keys() {
+        balances.insert(*address, public_balance_per_validator);
+    }
+    let mut rng = TestRng::default();
+    let gen_ledger = genesis_ledger(*gen_key, committee.clone(), balances.clone(), node_id, &mut rng);
+    let ledger = Arc::new(TranslucentLedgerService::new(gen_ledger, Arc::new(AtomicBool::new(false))));
+    match epoch_length {
+        Some(epoch_length) => {
+            let _ = EPOCH_LENGTH.set(epoch_length);
+            Ok(Arc::new(EpochLedgerService::new(ledger, epoch_length, committee_schedule(&committee))?))
+        }
+        None => Ok(ledger),
+    }
+}
+
+/// The number of rounds in an epoch, if the committee rotates.
+static EPOCH_LENGTH: OnceLock<u64> = OnceLock::new();
+
+/// Returns the committee schedule for the epochs, which deterministically rotates the stakes of the validators,
+/// such that the validator with the highest stake changes every epoch.
+fn committee_schedule(committee: &Committee<CurrentNetwork>) -> Vec<CommitteeMembers<CurrentNetwork>> {
+    let num_members = committee.members().len();
+    (0..num_members)
+        .map(|offset| {
+            committee
+                .members()
+                .iter()
+                .enumerate()
+                .map(|(index, (address, (_, is_open, commission)))| {
+                    let stake = MIN_VALIDATOR_STAKE * (1 + ((index + offset) % num_members) as u64);
+                    (*address, (stake, *is_open, *commission))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Watches the epoch of the node's round, logs each transition, and ensures the committee of the next epoch exists.
+fn watch_epochs(primary: Primary<CurrentNetwork>, epoch_length: u64) {
+    spawn_task("epoch watcher", async move {
+        let mut epoch = primary.current_round() / epoch_length;
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            let current_epoch = primary.current_round() / epoch_length;
+            if current_epoch != epoch {
+                epoch = current_epoch;
+                match primary.ledger().get_committee_for_round(epoch * epoch_length) {
+                    Ok(committee) => info!("Advanced to epoch {epoch} (committee '{}')", committee.id()),
+                    Err(e) => error!("Advanced to epoch {epoch} without a committee - {e}"),
+                }
+                // Ensure the committee of the next epoch exists, before the transition.
+                if let Err(e) = primary.ledger().get_committee_for_round((epoch + 1) * epoch_length) {
+                    error!("The committee for epoch {} is missing - {e}", epoch + 1);
+                }
+            }
+        }
+    });
+}
+
+pub type CurrentLedger = Ledger<CurrentNetwork, ConsensusMemory<CurrentNetwork>>;
+
+fn genesis_cache() -> &'static Mutex<HashMap<Vec<u8>, Block<CurrentNetwork>>> {
+    static CACHE: OnceLock<Mutex<HashMap<Vec<u8>, Block<CurrentNetwork>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn genesis_block(
+    genesis_private_key: PrivateKey<CurrentNetwork>,
+    committee: Committee<CurrentNetwork>,
+    public_balances: IndexMap<Address<CurrentNetwork>, u64>,
+    rng: &mut (impl Rng + CryptoRng),
+) -> Block<CurrentNetwork> {
+    // Initialize the store.
+    let store = ConsensusStore::<_, ConsensusMemory<_>>::open(None).unwrap();
+    // Initialize a new VM.
+    let vm = VM::from(store).unwrap();
+    // Initialize the genesis block.
+    let bonded_balances: IndexMap<_, _> =
+        committee.members().iter().map(|(address, (amount, _, _))| (*address, (*address, *address, *amount))).collect();
+    vm.genesis_quorum(&genesis_private_key, committee, public_balances, bonded_balances, rng).unwrap()
+}
+
+fn genesis_ledger(
+    genesis_private_key: PrivateKey<CurrentNetwork>,
+    committee: Committee<CurrentNetwork>,
+    public_balances: IndexMap<Address<CurrentNetwork>, u64>,
+    node_id: u16,
+    rng: &mut (impl Rng + CryptoRng),
+) -> CurrentLedger {
+    let cache_key =
+        to_bytes_le![genesis_private_key, committee, public_balances.iter().collect::<Vec<(_, _)>>()].unwrap();
+    // Initialize the genesis block on the first call; other callers
+    // will wait for it on the mutex.
+    let block = genesis_cache()
+        .lock()
+        .unwrap()
+        .entry(cache_key.clone())
+        .or_insert_with(|| {
+            let hasher = BHP256::<CurrentNetwork>::setup("aleo.dev.block").unwrap();
+            let file_name = hasher.hash(&cache_key.to_bits_le()).unwrap().to_string() + ".genesis";
+            let file_path = std::env::temp_dir().join(file_name);
+            if file_path.exists() {
+                let buffer = std::fs::read(file_path).unwrap();
+                return Block::from_bytes_le(&buffer).unwrap();
+            }
+
+            let block = genesis_block(genesis_private_key, committee, public_balances, rng);
+            std::fs::write(&file_path, block.to_bytes_le().unwrap()).unwrap();
+            block
+        })
+        .clone();
+    // Initialize the ledger with the genesis block.
+    CurrentLedger::load(block, aleo_std::StorageMode::Development(node_id)).unwrap()
+}
+
+/// The domain separator of the derivation of the committee accounts from a master seed.
+const ACCOUNT_DERIVATION_DOMAIN: &[u8] = b"snarkos-simple-node-account";
+
+/// Parses a hex-encoded master seed, with an optional `0x` prefix.
+fn parse_master_seed(master_seed: &str) -> Result<Vec<u8>> {
+    let hex = master_seed.strip_prefix("0x").unwrap_or(master_seed);
+    ensure!(
+        !hex.is_empty() && hex.len() % 2 == 0 && hex.bytes().all(|byte| byte.is_ascii_hexdigit()),
+        "Invalid master seed '{master_seed}' - expected a non-empty, even-length hex string"
+    );
+    (0..hex.len()).step_by(2).map(|i| Ok(u8::from_str_radix(&hex[i..i + 2], 16)?)).collect()
+}
+
+/// Samples the account of the committee member with the given index.
+///
+/// If a master seed is given, the account of member `i` is derived as follows:
+/// 1. The member seed is `SHA-256(ACCOUNT_DERIVATION_DOMAIN || master_seed || i)`, with `i` as a little-endian `u16`.
+/// 2. The private key is sampled from a ChaCha20 RNG seeded with the 32-byte member seed.
+///
+/// Otherwise, the private key is sampled from a ChaCha20 RNG seeded with `seed_base + i`.
+fn sample_account(master_seed: Option<&[u8]>, seed_base: u64, index: u16) -> Result<Account<CurrentNetwork>> {
+    let mut rng = match master_seed {
+        Some(master_seed) => {
+            let member_seed = Sha256::new()
+                .chain_update(ACCOUNT_DERIVATION_DOMAIN)
+                .chain_update(master_seed)
+                .chain_update(index.to_le_bytes())
+                .finalize();
+            rand_chacha::ChaChaRng::from_seed(member_seed.into())
+        }
+        None => rand_chacha::ChaChaRng::seed_from_u64(seed_base.wrapping_add(index as u64)),
+    };
+    Account::new(&mut rng)
+}
+
+/// Initializes the components of the node.
+/// Note: If `--quiet` is set, the account and validators are logged at debug level, instead of printed to stdout.
+/// If `--dump-config` is set, the committee is also printed to stdout, one `node_id=ip:port,address` line per member.
+fn initialize_components(
+    args: &Args,
+    peers: &HashMap<u16, SocketAddr>,
+) -> Result<(Committee<CurrentNetwork>, Account<CurrentNetwork>)> {
+    let (node_id, num_nodes) = (args.id, args.num_nodes);
+    // Ensure that the node ID is valid.
+    ensure!(node_id < num_nodes, "Node ID {node_id} must be less than {num_nodes}");
+
+    // Parse the master seed, if one was provided.
+    let master_seed = args.master_seed.as_deref().map(parse_master_seed).transpose()?;
+    // Sample a account.
+    let account = sample_account(master_seed.as_deref(), args.seed_base, node_id)?;
+    // If an expected address was provided, ensure it matches the account.
+    if let Some(expected) = &args.expect_address {
+        let expected = Address::<CurrentNetwork>::from_str(expected)?;
+        ensure!(
+            account.address() == expected,
+            "Node {node_id} has address {}, but the expected address is {expected}",
+            account.address()
+        );
+    }
+    if args.quiet {
+        debug!("Node {node_id} is running with account:\n{account}");
+    } else {
+        println!("\n{account}\n");
+    }
+
+    // Initialize a map for the committee members.
+    let mut members = IndexMap::with_capacity(num_nodes as usize);
+    // Initialize a list for the addresses of the validators, in the order of their node IDs.
+    let mut addresses = Vec::with_capacity(num_nodes as usize);
+    // Add the validators as members.
+    for i in 0..num_nodes {
+        // Sample the account.
+        let account = sample_account(master_seed.as_deref(), args.seed_base, i)?;
+        // Add the validator.
+        members.insert(account.address(), (MIN_VALIDATOR_STAKE, false, i as u8));
+        addresses.push(account.address());
+        if args.quiet {
+            debug!("  Validator {}: {}", i, account.address());
+        } else {
+            println!("  Validator {}: {}", i, account.address());
+        }
+    }
+    if !args.quiet {
+        println!();
+    }
+
+    // Initialize the committee.
+    let committee = Committee::<CurrentNetwork>::new(0u64, members)?;
+    // If requested, print the committee as a ready-to-reuse peers file, one `node_id=ip:port,address` line per member.
+    if args.dump_config {
+        let members = (0..num_nodes).zip(addresses).map(|(i, address)| {
+            let ip = match peers.get(&i) {
+                Some(ip) => *ip,
+                None => SocketAddr::from((Ipv4Addr::LOCALHOST, MEMORY_POOL_PORT + i)),
+            };
+            (i, ip, address)
+        });
+        print!("{}", format_peers(members));
+    }
+    // If a maximum stake fraction was provided, warn about the members holding more than it.
+    if let Some(max_fraction) = args.max_stake_fraction {
+        ensure!((0.0..=1.0).contains(&max_fraction), "The maximum stake fraction must be between 0 and 1");
+        for (address, (stake, _, _)) in committee.members() {
+            let fraction = *stake as f64 / committee.total_stake() as f64;
+            if fraction > max_fraction {
+                warn!(
+                    "Validator {address} holds {:.1}% of the committee stake (maximum: {:.1}%)",
+                    fraction * 100.0,
+                    max_fraction * 100.0
+                );
+            }
+        }
+    }
+    // Return the committee and account.
+    Ok((committee, account))
+}
+
+/// Handles the consensus receiver.
+fn consensus_handler(receiver: ConsensusReceiver<CurrentNetwork>) {
+    let ConsensusReceiver { mut rx_consensus_subdag } = receiver;
+
+    spawn_task("consensus handler", async move {
+        while let Some((subdag, transmissions, callback)) = rx_consensus_subdag.recv().await {
+            // Determine the amount of time to sleep for the subdag.
+            let subdag_ms = subdag.values().flatten().count();
+            // Determine the amount of time to sleep for the transmissions.
+            let transmissions_ms = transmissions.len() * 25;
+            // Add a constant delay.
+            let constant_ms = 100;
+            // Compute the total amount of time to sleep.
+            let sleep_ms = (subdag_ms + transmissions_ms + constant_ms) as u64;
+            // Sleep for the determined amount of time.
+            sleep_scaled(std::time::Duration::from_millis(sleep_ms)).await;
+            // Call the callback.
+            callback.send(Ok(())).ok();
+        }
+    });
+}
+
+/// Returns the trusted validators.
+fn trusted_validators(node_id: u16, num_nodes: u16, peers: HashMap<u16, SocketAddr>) -> Vec<SocketAddr> {
+    // Initialize a vector for the trusted nodes.
+    let mut trusted = Vec::with_capacity(num_nodes as usize);
+    // Iterate through the nodes.
+    for i in 0..num_nodes {
+        // Initialize the gateway IP.
+        let ip = match peers.get(&i) {
+            Some(ip) => *ip,
+            None => SocketAddr::from_str(&format!("127.0.0.1:{}", MEMORY_POOL_PORT + i)).unwrap(),
+        };
+        // If the node is not the current node, add it to the trusted nodes.
+        if i != node_id {
+            trusted.push(ip);
+        }
+    }
+    // Return the trusted nodes.
+    trusted
+}
+
+/// Handles OS signals for the in-process nodes (keyed by node ID) to intercept and perform a clean shutdown.
+/// If a metrics path is given, with the instant and round the run started at, the metrics of the first node
+/// are exported to it before the shutdown.
+/// Note: Only Ctrl-C is supported; it should work on both Unix-family systems and Windows.
+fn handle_signals(nodes: Vec<(u16, Primary<CurrentNetwork>)>, metrics_out: Option<(PathBuf, std::time::Instant, u64)>) {
+    spawn_task("signal handler", async move {
+        let exit_code = tokio::select! {
+            result = tokio::signal::ctrl_c() => match result {
+                Ok(()) => 0,
+                Err(error) => {
+                    error!("tokio::signal::ctrl_c encountered an error: {}", error);
+                    return;
+                }
+            },
+            // A spawned task panicked, and requested the shutdown of the node.
+            _ = SHUTDOWN_REQUESTED.notified() => 1,
+        };
+        // Report the send counts of the emulated clients.
+        #[cfg(feature = "cannons")]
+        cannons::log_client_summary();
+        // Export the metrics of the run, if requested.
+        if let (Some((path, started_at, start_round)), Some((_, primary))) = (&metrics_out, nodes.first()) {
+            match export_run_metrics(primary, path, *started_at, *start_round) {
+                Ok(()) => info!("Exported the metrics of the run to '{}'", path.display()),
+                Err(error) => error!("Failed to export the metrics of the run to '{}' - {error}", path.display()),
+            }
+        }
+        shut_down_in_order(nodes).await;
+        std::process::exit(exit_code);
+    });
+}
+
+/// The factor the example's delays are accelerated by, stored as the bits of an `f64` (`0` stands for `1.0`).
+static TIME_SCALE: AtomicU64 = AtomicU64::new(0);
+
+/// Sets the factor the example's delays (i.e. the cannons, the warm-up and the stagger) are accelerated by.
+/// For example, a scale of `2.0` halves these delays. The delays of the memory pool itself are not scaled.
+fn set_time_scale(scale: f64) -> Result<()> {
+    ensure!(scale.is_finite() && scale > 0.0, "The time scale must be a positive number, found {scale}");
+    TIME_SCALE.store(scale.to_bits(), Ordering::SeqCst);
+    Ok(())
+}
+
+/// Returns the given delay, accelerated by the given scale.
+fn scale_duration_by(duration: std::time::Duration, scale: f64) -> std::time::Duration {
+    if scale == 1.0 { duration } else { duration.div_f64(scale) }
+}
+
+/// Sleeps for the given delay, accelerated by the time scale.
+async fn sleep_scaled(duration: std::time::Duration) {
+    let scale = match TIME_SCALE.load(Ordering::Relaxed) {
+        0 => 1.0,
+        bits => f64::from_bits(bits),
+    };
+    tokio::time::sleep(scale_duration_by(duration, scale)).await
+}
+
+/// Whether a panic in a spawned task shuts down the node, instead of leaving it running without the task.
+static SHUTDOWN_ON_PANIC: AtomicBool = AtomicBool::new(false);
+/// Notified when a spawned task panicked, and the node should shut down.
+static SHUTDOWN_REQUESTED: tokio::sync::Notify = tokio::sync::Notify::const_new();
+
+/// Spawns the given future as a named task, and logs an error with the task name if it panics.
+/// If `SHUTDOWN_ON_PANIC` is set, the panic also triggers a clean shutdown of the node.
+fn spawn_task<F>(name: &'static str, future: F)
+where
+    F: std::future::Future<Output = ()> + Send + 'static,
+{
+    let handle = tokio::task::spawn(future);
+    tokio::task::spawn(async move {
+        let Err(error) = handle.await else { return };
+        // Note: Tasks are only cancelled when the runtime shuts down, which is not a failure.
+        if !error.is_panic() {
+            return;
+        }
+        let panic = error.into_panic();
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        error!("The {name} task panicked - {message}");
+        if SHUTDOWN_ON_PANIC.load(Ordering::Relaxed) {
+            error!("Shutting down the node, as the {name} task panicked");
+            // Note: `notify_one` stores a permit, in case the signal handler is not waiting yet.
+            SHUTDOWN_REQUESTED.notify_one();
+        }
+    });
+}
+
+/// Returns the number of certificates each member of the current committee authored in the given rounds,
+/// starting from the committee members, so that the absent validators are reported too.
+fn count_participation(primary: &Primary<CurrentNetwork>, from: u64, to: u64) -> Result<IndexMap<String, u64>> {
+    let storage = primary.storage();
+    let committee = primary.ledger().current_committee()?;
+    let mut counts = committee.members().keys().map(|address| (address.to_string(), 0u64)).collect::<IndexMap<_, _>>();
+    for round in from..=to {
+        for certificate in storage.get_certificates_for_round(round) {
+            *counts.entry(certificate.author().to_string()).or_default() += 1;
+        }
+    }
+    Ok(counts)
+}
+
+/// Writes the metrics of the run to the given path, as a JSON object with the final round, the rounds per second
+/// since the start of the run, the fired and dropped transmissions, the time (in UNIX milliseconds) the node reached
+/// each retained round, and the number of certificates each committee member authored in the retained rounds.
+fn export_run_metrics(
+    primary: &Primary<CurrentNetwork>,
+    path: &Path,
+    started_at: std::time::Instant,
+    start_round: u64,
+) -> Result<()> {
+    let storage = primary.storage();
+    let final_round = primary.current_round();
+    let elapsed_secs = started_at.elapsed().as_secs_f64();
+    let rounds_per_sec =
+        if elapsed_secs > 0.0 { final_round.saturating_sub(start_round) as f64 / elapsed_secs } else { 0.0 };
+    let round_timestamps = storage
+        .round_timestamps()
+        .into_iter()
+        .map(|(round, timestamp)| (round.to_string(), json!(timestamp)))
+        .collect::<serde_json::Map<_, _>>();
+    let participation = count_participation(primary, storage.gc_round() + 1, final_round)?;
+    let metrics = json!({
+        "final_round": final_round,
+        "elapsed_secs": elapsed_secs,
+        "rounds_per_sec": rounds_per_sec,
+        "fired": NUM_FIRED.load(Ordering::Relaxed),
+        "dropped": NUM_DROPPED.load(Ordering::Relaxed),
+        "stuck": NUM_STUCK.load(Ordering::Relaxed),
+        "dropped_inbound": primary.gateway().num_dropped_inbound(),
+        "round_timestamps": round_timestamps,
+        "participation": participation,
+    });
+    std::fs::write(path, serde_json::to_string_pretty(&metrics)?)?;
+    Ok(())
+}
+
+/// Shuts down the given in-process nodes one at a time, highest ID first, awaiting each shutdown in turn.
+/// Note: A defined order keeps the teardown of an in-process cluster reproducible.
+async fn shut_down_in_order(mut nodes: Vec<(u16, Primary<CurrentNetwork>)>) {
+    nodes.sort_unstable_by_key(|(id, _)| std::cmp::Reverse(*id));
+    for (id, primary) in nodes {
+        primary.shut_down().await;
+        info!("Node {id} has shut down");
+    }
+}
+
+/**************************************************************************************************/
+
+/// The total number of transmissions fired by this node.
+static NUM_FIRED: AtomicU64 = AtomicU64::new(0);
+/// The total number of fired transmissions whose callback was dropped by the primary.
+static NUM_DROPPED: AtomicU64 = AtomicU64::new(0);
+/// The total number of fired transmissions whose callback the primary did not answer in time (i.e. stuck).
+static NUM_STUCK: AtomicU64 = AtomicU64::new(0);
+
+/**************************************************************************************************/
+
+/// Returns the current round, and the highest round observed among the peers' certificates in storage.
+fn network_round(primary: &Primary<CurrentNetwork>) -> (u64, u64) {
+    let current_round = primary.current_round();
+    // Determine the highest round of the peers' certificates.
+    let network_round = primary
+        .storage()
+        .highest_certificate_round_excluding(primary.gateway().account().address())
+        .unwrap_or_default()
+        .max(current_round);
+    (current_round, network_round)
+}
+
+/// Whether the node currently lags behind its peers by more than the maximum lag.
+static IS_LAGGING: AtomicBool = AtomicBool::new(false);
+
+/// Periodically compares the node's round against its peers', and alerts if the gap exceeds `max_lag_rounds`.
+/// Note: A gap of more than twice the maximum lag is logged as an error.
+fn watch_round_lag(primary: Primary<CurrentNetwork>, max_lag_rounds: u64) {
+    spawn_task("round lag watcher", async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            let (current_round, network_round) = network_round(&primary);
+            let gap = network_round - current_round;
+            if gap > max_lag_rounds.saturating_mul(2) {
+                error!("Node is {gap} rounds behind its peers (round {current_round}, network round {network_round})");
+            } else if gap > max_lag_rounds {
+                warn!("Node is {gap} rounds behind its peers (round {current_round}, network round {network_round})");
+            }
+            IS_LAGGING.store(gap > max_lag_rounds, Ordering::Relaxed);
+        }
+    });
+}
+
+/// Whether the node is currently offline, as it simulates a flapping validator.
+static IS_OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Simulates a flapping validator: every `interval_secs`, the node goes offline for `interval_secs`, i.e. it isolates
+/// and disconnects all of its peers, before letting the gateway reconnect to them.
+/// Note: This is intended for testing the tolerance of the cluster to churn only.
+fn flap_connections(primary: Primary<CurrentNetwork>, interval_secs: u64) {
+    spawn_task("connection flapper", async move {
+        let interval = std::time::Duration::from_secs(interval_secs);
+        loop {
+            tokio::time::sleep(interval).await;
+            // Isolate the peers, so that they do not reconnect while the node is offline, and disconnect them.
+            let gateway = primary.gateway();
+            let mut peers = gateway.connected_peers().read().clone();
+            peers.extend(gateway.trusted_validators());
+            warn!("Going offline for {interval_secs} seconds (disconnecting from {} peers)", peers.len());
+            IS_OFFLINE.store(true, Ordering::Relaxed);
+            for peer_ip in &peers {
+                gateway.isolate_peer(*peer_ip);
+                gateway.disconnect(*peer_ip);
+            }
+            tokio::time::sleep(interval).await;
+            // Lift the isolation, letting the gateway reconnect to the peers.
+            for peer_ip in &peers {
+                gateway.rejoin_peer(*peer_ip);
+            }
+            IS_OFFLINE.store(false, Ordering::Relaxed);
+            info!("Back online, reconnecting to {} peers", peers.len());
+        }
+    });
+}
+
+/// Periodically checks that the node's round advances, and alerts once it has not for `timeout_secs`.
+/// If a webhook is given, a JSON payload with the node ID, the stalled round and the elapsed time is posted to it.
+fn watch_stalls(primary: Primary<CurrentNetwork>, node_id: u16, timeout_secs: u64, webhook: Option<String>) {
+    spawn_task("stall watcher", async move {
+        #[cfg(feature = "webhook")]
+        let client = reqwest::Client::new();
+        let mut last_round = primary.current_round();
+        let mut last_advance = std::time::Instant::now();
+        let mut is_stalled = false;
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            let round = primary.current_round();
+            if round != last_round {
+                if is_stalled {
+                    info!("Node {node_id} resumed at round {round}");
+                }
+                (last_round, last_advance, is_stalled) = (round, std::time::Instant::now(), false);
+                continue;
+            }
+            // Only alert once per stall.
+            let elapsed_secs = last_advance.elapsed().as_secs();
+            if is_stalled || elapsed_secs < timeout_secs {
+                continue;
+            }
+            is_stalled = true;
+            error!("Node {node_id} has stalled at round {round} for {elapsed_secs} seconds");
+            #[cfg(feature = "webhook")]
+            if let Some(url) = &webhook {
+                let payload = json!({ "node_id": node_id, "round": round, "elapsed_secs": elapsed_secs });
+                notify_webhook(&client, url, &payload).await;
+            }
+            #[cfg(not(feature = "webhook"))]
+            let _ = &webhook;
+        }
+    });
+}
+
+/// Posts the given payload to the webhook, retrying a bounded number of times with an exponential backoff.
+#[cfg(feature = "webhook")]
+async fn notify_webhook(client: &reqwest::Client, url: &str, payload: &serde_json::Value) {
+    // The maximum number of attempts to notify the webhook.
+    const MAX_ATTEMPTS: u32 = 3;
+
+    for attempt in 1..=MAX_ATTEMPTS {
+        match client.post(url).json(payload).timeout(std::time::Duration::from_secs(5)).send().await {
+            Ok(response) if response.status().is_success() => return,
+            Ok(response) => warn!("The webhook rejected the notification (attempt {attempt}) - {}", response.status()),
+            Err(e) => warn!("Failed to notify the webhook (attempt {attempt}) - {e}"),
+        }
+        if attempt < MAX_ATTEMPTS {
+            tokio::time::sleep(std::time::Duration::from_secs(1 << (attempt - 1))).await;
+        }
+    }
+    error!("Giving up on notifying the webhook at '{url}' after {MAX_ATTEMPTS} attempts");
+}
+
+/// The number of times a committed round was observed with a different leader than previously observed.
+static NUM_LEADER_MISMATCHES: AtomicU64 = AtomicU64::new(0);
+
+/// Watches the committed anchors of the BFT, and logs an error if the leader of a previously-committed round changes.
+/// Note: Only the rounds retained in storage can be compared, as older anchors are garbage collected.
+fn watch_committed_leaders(bft: BFT<CurrentNetwork>) {
+    spawn_task("leader watcher", async move {
+        let mut previous = bft.committed_anchors();
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            let current = bft.committed_anchors();
+            for (round, leader) in &current {
+                match previous.get(round) {
+                    Some(previous_leader) if previous_leader != leader => {
+                        let num_mismatches = NUM_LEADER_MISMATCHES.fetch_add(1, Ordering::Relaxed) + 1;
+                        error!(
+                            "Safety violation - the committed leader of round {round} changed from '{previous_leader}' to '{leader}' ({num_mismatches} in total)"
+                        );
+                        #[cfg(feature = "metrics")]
+                        metrics::increment_counter(metrics::bft::COMMITTED_LEADER_MISMATCHES);
+                    }
+                    _ => (),
+                }
+            }
+            previous = current;
+        }
+    });
+}
+
+/// Watches the peer files for changes, and connects to the peers whose addresses were added or changed.
+/// Note: The committee members are derived from the ledger, so only the peer topology is reloaded.
+/// To guard against partial writes, a change is only applied once the files are unchanged for one
+/// poll interval, and are valid.
+fn watch_peer_files(primary: Primary<CurrentNetwork>, args: Args, mut peers: HashMap<u16, SocketAddr>) {
+    // Reads and validates the peer files.
+    fn read_peers(args: &Args) -> Result<HashMap<u16, SocketAddr>> {
+        let peers = merge_peers(
+            args.peers.iter().map(|path| parse_peers(std::fs::read_to_string(path)?)).collect::<Result<Vec<_>>>()?,
+        )?;
+        if let Some(node_id) = peers.keys().find(|node_id| **node_id >= args.num_nodes) {
+            return Err(anyhow!("Node ID {node_id} must be less than {}", args.num_nodes));
+        }
+        Ok(peers)
+    }
+
+    spawn_task("peer file watcher", async move {
+        let mut pending: Option<HashMap<u16, SocketAddr>> = None;
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            let candidate = match read_peers(&args) {
+                Ok(candidate) => candidate,
+                Err(e) => {
+                    debug!("Ignoring the peer files, as they are invalid - {e}");
+                    pending = None;
+                    continue;
+                }
+            };
+            // Wait until the files are unchanged for one poll interval.
+            if candidate == peers || pending.as_ref() != Some(&candidate) {
+                pending = (candidate != peers).then_some(candidate);
+                continue;
+            }
+            // Apply the change.
+            for (node_id, ip) in &candidate {
+                if *node_id != args.id && peers.get(node_id) != Some(ip) {
+                    info!("Peer files changed - connecting to node {node_id} at '{ip}'");
+                    primary.gateway().connect(*ip);
+                }
+            }
+            for node_id in peers.keys().filter(|node_id| !candidate.contains_key(node_id)) {
+                info!("Peer files changed - node {node_id} was removed");
+            }
+            peers = candidate;
+            pending = None;
+        }
+    });
+}
+
+/// The number of checkpoint files rotated through.
+const NUM_CHECKPOINTS: usize = 3;
+
+/// Returns the path of the checkpoint file in the given slot.
+fn checkpoint_path(storage_path: &Path, node_id: u16, slot: usize) -> PathBuf {
+    storage_path.join(format!("checkpoint-{node_id}-{slot}"))
+}
+
+/// Periodically checkpoints the node's pending certificates and proposals to a rotating set of files.
+/// Note: Each checkpoint is written to a temporary file first, and renamed, so that a crash never corrupts it.
+fn checkpoint_periodically(primary: Primary<CurrentNetwork>, storage_path: PathBuf, node_id: u16, interval_secs: u64) {
+    spawn_task("checkpointer", async move {
+        let interval = std::time::Duration::from_secs(interval_secs.max(1));
+        for slot in (0..NUM_CHECKPOINTS).cycle() {
+            tokio::time::sleep(interval).await;
+            let path = checkpoint_path(&storage_path, node_id, slot);
+            let temp_path = path.with_extension("tmp");
+            let result = primary
+                .proposal_cache()
+                .await
+                .to_bytes_le()
+                .and_then(|bytes| Ok(std::fs::write(&temp_path, bytes)?))
+                .and_then(|_| Ok(std::fs::rename(&temp_path, &path)?));
+            match result {
+                Ok(()) => debug!("Checkpointed the proposal cache to '{}'", path.display()),
+                Err(e) => warn!("Failed to checkpoint the proposal cache to '{}' - {e}", path.display()),
+            }
+        }
+    });
+}
+
+/// Returns the modification time, path, bytes and contents of the newest valid checkpoint in the storage path.
+fn newest_checkpoint(
+    storage_path: &Path,
+    node_id: u16,
+    address: Address<CurrentNetwork>,
+) -> Option<(std::time::SystemTime, PathBuf, Vec<u8>, ProposalCache<CurrentNetwork>)> {
+    let mut newest = None;
+    for slot in 0..NUM_CHECKPOINTS {
+        let path = checkpoint_path(storage_path, node_id, slot);
+        let (Ok(bytes), Ok(modified)) = (std::fs::read(&path), path.metadata().and_then(|m| m.modified())) else {
+            continue;
+        };
+        // Skip the checkpoints that are corrupted, or belong to another account.
+        let proposal_cache = match ProposalCache::<CurrentNetwork>::from_bytes_le(&bytes) {
+            Ok(proposal_cache) if proposal_cache.is_valid(address) => proposal_cache,
+            _ => {
+                warn!("Skipping the invalid checkpoint at '{}'", path.display());
+                continue;
+            }
+        };
+        if newest.as_ref().map_or(true, |(newest_modified, ..)| modified > *newest_modified) {
+            newest = Some((modified, path, bytes, proposal_cache));
+        }
+    }
+    newest
+}
+
+/// Installs the newest valid checkpoint in the storage path (if any) as the proposal cache,
+/// which the primary loads on startup.
+/// Note: If the primary persisted a proposal cache (i.e. on a clean shutdown) after the newest checkpoint,
+/// that proposal cache is fresher, and is kept instead.
+/// If a ledger is given, the integrity of the proposal cache the primary loads (i.e. the installed checkpoint,
+/// or else the existing proposal cache) is verified against it, and the node refuses to start if any check fails.
+fn restore_checkpoint(
+    storage_path: Option<&Path>,
+    node_id: u16,
+    address: Address<CurrentNetwork>,
+    dev: Option<u16>,
+    verify_with: Option<&dyn LedgerService<CurrentNetwork>>,
+) -> Result<()> {
+    let cache_path = proposal_cache_path(CurrentNetwork::ID, dev);
+    if let Some((modified, path, bytes, proposal_cache)) =
+        storage_path.and_then(|storage_path| newest_checkpoint(storage_path, node_id, address))
+    {
+        // Keep the proposal cache persisted by the primary, if it is newer than the checkpoint.
+        if cache_path.metadata().and_then(|m| m.modified()).is_ok_and(|cache_modified| cache_modified >= modified) {
+            info!("Skipping the checkpoint at '{}' (the proposal cache is newer)", path.display());
+        } else {
+            // If requested, verify the integrity of the checkpoint before installing it.
+            if let Some(ledger) = verify_with {
+                verify_integrity(&proposal_cache, ledger, &path)?;
+            }
+            std::fs::write(cache_path, bytes)?;
+            info!("Restored the checkpoint at '{}'", path.display());
+            return Ok(());
+        }
+    }
+    // If requested, verify the integrity of the existing proposal cache (if any), which the primary loads instead.
+    if let Some(ledger) = verify_with {
+        match std::fs::read(&cache_path) {
+            Ok(bytes) => {
+                let proposal_cache = ProposalCache::<CurrentNetwork>::from_bytes_le(&bytes).map_err(|e| {
+                    anyhow!("Refusing to start from the unreadable proposal cache at '{}' - {e}", cache_path.display())
+                })?;
+                verify_integrity(&proposal_cache, ledger, &cache_path)?;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => info!("There is no proposal cache to verify"),
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(())
+}
+
+/// Verifies the integrity of the given proposal cache (loaded from the given path) against the ledger,
+/// logging each failed check, and returns an error if any check fails.
+fn verify_integrity(
+    proposal_cache: &ProposalCache<CurrentNetwork>,
+    ledger: &dyn LedgerService<CurrentNetwork>,
+    path: &Path,
+) -> Result<()> {
+    let failures = proposal_cache.check_integrity(ledger);
+    for failure in &failures {
+        error!("The proposal cache at '{}' failed an integrity check - {failure}", path.display());
+    }
+    ensure!(failures.is_empty(), "Refusing to start from the corrupted proposal cache at '{}'", path.display());
+    info!("Verified the integrity of the proposal cache at '{}'", path.display());
+    Ok(())
+}
+
+/// Removes the checkpoints of the node in the storage path, so that they are not restored.
+#[cfg(feature = "rest")]
+fn remove_checkpoints(storage_path: &Path, node_id: u16) -> Result<()> {
+    for slot in 0..NUM_CHECKPOINTS {
+        let path = checkpoint_path(storage_path, node_id, slot);
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
+
+/// The number of certificates and transmissions retained for a round, and their estimated size in bytes.
+#[derive(Default)]
+struct RoundSize {
+    num_certificates: usize,
+    num_transmissions: usize,
+    num_bytes: usize,
+}
+
+/// Returns the estimated size of each retained round in storage.
+/// Note: Each transmission is only counted once, in the first round that references it.
+fn estimate_storage_size(storage: &Storage<CurrentNetwork>) -> Result<IndexMap<u64, RoundSize>> {
+    // Initialize the map of round sizes.
+    let mut sizes = IndexMap::new();
+    // Initialize a set for the transmission IDs that were already counted.
+    let mut seen_transmission_ids = HashSet::<TransmissionID<CurrentNetwork>>::new();
+    // Iterate over the retained rounds.
+    for round in storage.gc_round().saturating_add(1)..=storage.current_round() {
+        let mut size = RoundSize::default();
+        for certificate in storage.get_certificates_for_round(round) {
+            size.num_certificates += 1;
+            size.num_bytes += certificate.to_bytes_le()?.len();
+            for transmission_id in certificate.transmission_ids() {
+                // Skip the transmission if it was already counted.
+                if !seen_transmission_ids.insert(*transmission_id) {
+                    continue;
+                }
+                if let Some(transmission) = storage.get_transmission(*transmission_id) {
+                    size.num_transmissions += 1;
+                    size.num_bytes += transmission_num_bytes(&transmission)?;
+                }
+            }
+        }
+        sizes.insert(round, size);
+    }
+    Ok(sizes)
+}
+
+/// Returns the size in bytes of the given transmission, reusing its buffer when it holds one,
+/// and only serializing the transmissions that are held as (deserialized) objects.
+fn transmission_num_bytes(transmission: &Transmission<CurrentNetwork>) -> Result<usize> {
+    fn num_bytes<T: FromBytes + ToBytes + Send + 'static>(data: &Data<T>) -> Result<usize> {
+        match data {
+            Data::Buffer(bytes) => Ok(bytes.len()),
+            Data::Object(object) => Ok(object.to_bytes_le()?.len()),
+        }
+    }
+    match transmission {
+        Transmission::Solution(solution) => num_bytes(solution),
+        Transmission::Transaction(transaction) => num_bytes(transaction),
+        Transmission::Ratification => Ok(0),
+    }
+}
+
+/// Whether the cannons are paused, as the storage approaches the memory budget.
+static CANNONS_PAUSED: AtomicBool = AtomicBool::new(false);
+
+/// Periodically estimates the memory used by the storage, and sheds load as it approaches `max_memory_mb`.
+/// Above 90% of the budget, the cannons are paused, and they resume once the usage drops below 75%.
+/// Note: The storage is garbage collected every `max_gc_rounds`, which the committee must agree on,
+/// so the load is shed by pausing the cannons until the retained rounds are collected.
+fn watch_memory_budget(primary: Primary<CurrentNetwork>, max_memory_mb: u64) {
+    spawn_task("memory budget watcher", async move {
+        let budget = max_memory_mb.saturating_mul(1024 * 1024);
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            // Estimate the memory used by the storage.
+            let num_bytes = match estimate_storage_size(primary.storage()) {
+                Ok(sizes) => sizes.values().map(|size| size.num_bytes as u64).sum::<u64>(),
+                Err(e) => {
+                    warn!("Failed to estimate the size of the storage - {e}");
+                    continue;
+                }
+            };
+            let usage_mb = num_bytes / (1024 * 1024);
+            let is_paused = CANNONS_PAUSED.load(Ordering::Relaxed);
+            if !is_paused && num_bytes > budget / 10 * 9 {
+                warn!("Pausing the cannons, as the storage uses {usage_mb} of {max_memory_mb} MB");
+                CANNONS_PAUSED.store(true, Ordering::Relaxed);
+            } else if is_paused && num_bytes < budget / 4 * 3 {
+                info!("Resuming the cannons, as the storage uses {usage_mb} of {max_memory_mb} MB");
+                CANNONS_PAUSED.store(false, Ordering::Relaxed);
+            }
+        }
+    });
+}
+
+/// Periodically logs a compact line with the node's round, peers, rounds/sec, fired count and queue depth.
+fn log_metrics_periodically(primary: Primary<CurrentNetwork>, interval_secs: u64) {
+    spawn_task("metrics logger", async move {
+        let interval = std::time::Duration::from_secs(interval_secs.max(1));
+        let mut last_round = primary.current_round();
+        loop {
+            tokio::time::sleep(interval).await;
+            // Compute the rounds per second since the last line.
+            let round = primary.current_round();
+            let rounds_per_sec = round.saturating_sub(last_round) as f64 / interval.as_secs_f64();
+            last_round = round;
+            info!(
+                "round={round} peers={} rounds/sec={rounds_per_sec:.2} fired={} dropped={} stuck={} queue={} \
+                 dropped_inbound={}",
+                primary.gateway().number_of_connected_peers(),
+                NUM_FIRED.load(Ordering::Relaxed),
+                NUM_DROPPED.load(Ordering::Relaxed),
+                NUM_STUCK.load(Ordering::Relaxed),
+                primary.num_unconfirmed_transmissions(),
+                primary.gateway().num_dropped_inbound(),
+            );
+        }
+    });
+}
+
+/// Periodically pushes the node's round, peers, rounds/sec and fired count to the given OTLP/HTTP collector
+/// (e.g. `http://localhost:4318`), encoded as OTLP JSON.
+#[cfg(feature = "otlp")]
+fn export_otlp_metrics(primary: Primary<CurrentNetwork>, node_id: u16, endpoint: String, interval_secs: u64) {
+    spawn_task("OTLP exporter", async move {
+        let url = format!("{}/v1/metrics", endpoint.trim_end_matches('/'));
+        let client = reqwest::Client::new();
+        let interval = std::time::Duration::from_secs(interval_secs.max(1));
+        let unix_nanos = || {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos()
+                .to_string()
+        };
+        let start_time = unix_nanos();
+        let mut last_round = primary.current_round();
+        loop {
+            tokio::time::sleep(interval).await;
+            // Compute the rounds per second since the last export.
+            let round = primary.current_round();
+            let rounds_per_sec = round.saturating_sub(last_round) as f64 / interval.as_secs_f64();
+            last_round = round;
+            // Note: OTLP JSON encodes 64-bit integers as strings.
+            let time = unix_nanos();
+            let gauge = |name: &str, value: serde_json::Value| {
+                let value_key = if value.is_f64() { "asDouble" } else { "asInt" };
+                json!({ "name": name, "gauge": { "dataPoints": [{ "timeUnixNano": time, (value_key): value }] } })
+            };
+            let metrics = json!([
+                gauge("snarkos.round", json!(round.to_string())),
+                gauge("snarkos.peers", json!(primary.gateway().number_of_connected_peers().to_string())),
+                gauge("snarkos.rounds_per_sec", json!(rounds_per_sec)),
+                {
+                    "name": "snarkos.fired",
+                    "sum": {
+                        "aggregationTemporality": 2,
+                        "isMonotonic": true,
+                        "dataPoints": [{
+                            "startTimeUnixNano": start_time,
+                            "timeUnixNano": time,
+                            "asInt": NUM_FIRED.load(Ordering::Relaxed).to_string(),
+                        }],
+                    },
+                },
+            ]);
+            let body = json!({
+                "resourceMetrics": [{
+                    "resource": {
+                        "attributes": [
+                            { "key": "service.name", "value": { "stringValue": "snarkos-simple-node" } },
+                            { "key": "node.id", "value": { "intValue": node_id.to_string() } },
+                        ],
+                    },
+                    "scopeMetrics": [{ "scope": { "name": "simple_node" }, "metrics": metrics }],
+                }],
+            });
+            // Push the metrics to the collector.
+            match client.post(&url).json(&body).send().await {
+                Ok(response) if !response.status().is_success() => {
+                    warn!("The OTLP collector rejected the metrics - {}", response.status())
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Failed to export the metrics to the OTLP collector - {e}"),
+            }
+        }
+    });
+}
+
+/**************************************************************************************************/
+
+/// Runs a short in-process cluster of BFT nodes, and ensures they progress past the target round,
+/// and agree on the leaders of the rounds they have committed.
+async fn run_self_test(args: &Args) -> Result<()> {
+    // The number of nodes in the cluster.
+    const NUM_NODES: u16 = 4;
+    // The round all nodes must advance past.
+    const TARGET_ROUND: u64 = 3;
+    // The maximum time to wait for the nodes to progress.
+    const TIMEOUT_IN_SECS: u64 = 120;
+
+    info!("Starting the self-test with {NUM_NODES} in-process nodes...");
+    // Start the nodes.
+    let mut nodes = Vec::with_capacity(NUM_NODES as usize);
+    for id in 0..NUM_NODES {
+        let node_args = Args { id, num_nodes: NUM_NODES, peers: vec![], ..args.clone() };
+        let (bft, sender) = start_bft(&node_args, Default::default()).await?;
+        // Fire a bit of load.
+        #[cfg(feature = "cannons")]
+        {
+            // The interval to fire transmissions at.
+            const INTERVAL_MS: u64 = 450; // ms
+            cannons::fire_unconfirmed_solutions(&sender, id, INTERVAL_MS, None, 0, 0.5);
+            cannons::fire_unconfirmed_transactions(&sender, id, INTERVAL_MS, None, 0, 0.5);
+        }
+        #[cfg(not(feature = "cannons"))]
+        drop(sender);
+        nodes.push(bft);
+    }
+    // Handle OS signals, shutting down the whole cluster.
+    handle_signals((0..NUM_NODES).zip(nodes.iter().map(|bft| bft.primary().clone())).collect(), None);
+
+    // Wait until all nodes have advanced past the target round, and committed an anchor.
+    let start = std::time::Instant::now();
+    loop {
+        let rounds = nodes.iter().map(|bft| bft.primary().current_round()).collect::<Vec<_>>();
+        if nodes.iter().all(|bft| bft.primary().current_round() > TARGET_ROUND && !bft.committed_anchors().is_empty()) {
+            info!("Self-test: all nodes advanced past round {TARGET_ROUND} (rounds: {rounds:?})");
+            break;
+        }
+        ensure!(
+            start.elapsed().as_secs() < TIMEOUT_IN_SECS,
+            "Self-test failed: the nodes did not advance past round {TARGET_ROUND} within {TIMEOUT_IN_SECS}s (rounds: {rounds:?})"
+        );
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    }
+
+    // Ensure the nodes agree on the leaders of the rounds they have all committed.
+    let anchors = nodes.iter().map(|bft| bft.committed_anchors()).collect::<Vec<_>>();
+    for (round, leader) in &anchors[0] {
+        for (i, node_anchors) in anchors.iter().enumerate().skip(1) {
+            if let Some(other_leader) = node_anchors.get(round) {
+                ensure!(
+                    leader == other_leader,
+                    "Self-test failed: nodes 0 and {i} committed different leaders for round {round} ({leader} vs. {other_leader})"
+                );
+            }
+        }
+    }
+
+    // Shut down the nodes, highest ID first.
+    for (id, bft) in nodes.iter().enumerate().rev() {
+        bft.shut_down().await;
+        info!("Node {id} has shut down");
+    }
+    info!("Self-test passed");
+    Ok(())
+}
+
+/**************************************************************************************************/
+
+/// The operating mode of the node.
+#[derive(Debug, Clone, ValueEnum)]
+enum Mode {
+    /// Runs the node with the Narwhal memory pool protocol.
+    Narwhal,
+    /// Runs the node with the Bullshark BFT protocol (on top of Narwhal).
+    Bft,
+}
+
+/// How received transmissions are materialized by the primary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Materialization {
+    /// Transmissions are kept as buffers, and only deserialized when needed.
+    Lazy,
+    /// Transmissions are deserialized into objects on ingest.
+    Eager,
+}
+
+/// The policy for staggering the startup of the nodes.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum StaggerPolicy {
+    /// Delays the startup by `100ms * id`.
+    Linear,
+    /// Delays the startup of every node by `100ms`.
+    Constant,
+    /// Does not delay the startup.
+    None,
+}
+
+impl StaggerPolicy {
+    /// The base stagger delay (in milliseconds).
+    const STAGGER_IN_MS: u64 = 100;
+
+    /// Returns the startup delay for the given node ID.
+    fn delay(self, node_id: u16) -> std::time::Duration {
+        let delay_ms = match self {
+            Self::Linear => Self::STAGGER_IN_MS * node_id as u64,
+            Self::Constant => Self::STAGGER_IN_MS,
+            Self::None => 0,
+        };
+        std::time::Duration::from_millis(delay_ms)
+    }
+}
+
+/// A simple CLI for the node.
+#[derive(Clone, Parser, Debug)]
+struct Args {
+    /// If set, the path to a TOML file with the options of the node (e.g. `num_nodes = 4`), overridden by the flags.
+    #[arg(long, value_name = "PATH")]
+    config_file: Option<PathBuf>,
+    /// The mode to run the node in.
+    #[arg(long)]
+    mode: Mode,
+    /// The ID of the node.
+    #[arg(long, value_name = "ID")]
+    id: u16,
+    /// The number of nodes in the network.
+    #[arg(long, value_name = "N")]
+    num_nodes: u16,
+    /// If set, the path to the file containing the committee peers; may be repeated to merge several files.
+    #[arg(long, value_name = "PATH")]
+    peers: Vec<PathBuf>,
+    /// If set, the address of node 0, from which the addresses of the other committee peers are derived
+    /// (i.e. for a homogeneous deployment with sequential addresses), without a peers file.
+    #[arg(long, value_name = "BASE_IP:BASE_PORT")]
+    peer_subnet: Option<SocketAddr>,
+    /// The part of the peer subnet address incremented by the node ID.
+    #[arg(long, value_enum, default_value = "ip")]
+    peer_subnet_increment: SubnetIncrement,
+    /// Enables the solution cannons, and optionally the interval in ms to run them on.
+    #[arg(long, value_name = "INTERVAL_MS")]
+    #[cfg(feature = "cannons")]
+    fire_solutions: Option<Option<u64>>,
+    /// Enables the transaction cannons, and optionally the interval in ms to run them on.
+    #[arg(long, value_name = "INTERVAL_MS")]
+    #[cfg(feature = "cannons")]
+    fire_transactions: Option<Option<u64>>,
+    /// Enables the solution and transaction cannons, and optionally the interval in ms to run them on.
+    #[arg(long, value_name = "INTERVAL_MS")]
+    #[cfg(feature = "cannons")]
+    fire_transmissions: Option<Option<u64>>,
+    /// If set, the total number of transmission bytes to fire, after which the cannons stop.
+    #[arg(long, value_name = "BYTES")]
+    #[cfg(feature = "cannons")]
+    fire_byte_budget: Option<u64>,
+    /// The number of times the cannons retry a transmission, if the primary drops its callback.
+    #[arg(long, value_name = "N", default_value = "0")]
+    #[cfg(feature = "cannons")]
+    fire_retries: u32,
+    /// The time in ms the cannons await the primary's callback for a transmission, before counting it as stuck
+    /// and moving on to the next one.
+    #[arg(long, value_name = "MS", default_value = "60000")]
+    #[cfg(feature = "cannons")]
+    callback_timeout_ms: u64,
+    /// The probability that the cannons fire a transmission shared by all nodes (a duplicate), instead of a unique one.
+    #[arg(long, value_name = "RATE", default_value = "0.5")]
+    #[cfg(feature = "cannons")]
+    duplicate_rate: f64,
+    /// **For stress testing only.** If set, the node re-submits the transmissions it receives from its peers
+    /// to its own primary under fresh IDs, amplifying the load (bounded by `--mirror-cap`).
+    #[arg(long, default_value = "false")]
+    #[cfg(feature = "cannons")]
+    mirror: bool,
+    /// The maximum number of transmissions re-fired by the mirror, in total.
+    #[arg(long, value_name = "N", default_value = "10000")]
+    #[cfg(feature = "cannons")]
+    mirror_cap: u64,
+    /// If set, the number of independent clients to emulate, each firing transactions from its own ID space.
+    /// The clients fire in addition to the cannons, and their send counts are logged on shutdown.
+    #[arg(long, value_name = "N")]
+    #[cfg(feature = "cannons")]
+    clients: Option<u16>,
+    /// The intervals in ms the clients fire at, as a comma-separated list (e.g. `100,450,1000`),
+    /// assigned to the clients in turn (default: 450).
+    #[arg(long, value_name = "INTERVAL_MS", value_delimiter = ',')]
+    #[cfg(feature = "cannons")]
+    client_interval_ms: Vec<u64>,
+    /// Enables the metrics exporter.
+    #[clap(long, default_value = "false")]
+    metrics: bool,
+    /// If set, the delay in ms between initializing the BFT and the primary's first proposal (only in BFT mode).
+    #[arg(long, value_name = "DELAY_MS")]
+    bft_warmup_ms: Option<u64>,
+    /// If set, the minimum interval in ms between two proposals of the primary (paces the rounds).
+    #[arg(long, value_name = "INTERVAL_MS")]
+    round_interval_ms: Option<u64>,
+    /// If set, the number of rounds a stored transmission is still rejected as a duplicate, even after it is
+    /// garbage collected (default: 0, only while retained in storage). Larger windows use more memory.
+    #[arg(long, value_name = "ROUNDS")]
+    dedup_window_rounds: Option<u64>,
+    /// If set, the number of missing certificates requested from a peer per gateway message, while catching up
+    /// (default: 1). Larger batches take fewer messages, at the cost of larger ones.
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    cert_request_batch: Option<u16>,
+    /// If set, the delay in ms this node injects before proposing and signing batches (simulates a straggler).
+    #[arg(long, value_name = "DELAY_MS")]
+    straggler_delay_ms: Option<u64>,
+    /// For testing only: if set, the offset in ms (positive or negative) applied to the timestamps of this node's
+    /// batches, for exercising the timestamp validation of its peers under clock skew.
+    #[arg(long, value_name = "SKEW_MS", allow_negative_numbers = true)]
+    clock_skew_ms: Option<i64>,
+    /// If set, the highest round this node proposes a batch for. Past it, the node stops proposing, but stays up
+    /// (signing its peers' proposals and serving its REST API), for inspecting the cluster's final state.
+    #[arg(long, value_name = "ROUND", value_parser = clap::value_parser!(u64).range(1..))]
+    max_propose_round: Option<u64>,
+    /// If set, a panic in one of the node's tasks shuts down the node, instead of only being logged.
+    #[arg(long, default_value = "false")]
+    shutdown_on_panic: bool,
+    /// If set, logs the account and validators at debug level, instead of printing them to stdout.
+    #[arg(long, default_value = "false")]
+    quiet: bool,
+    /// If set, prints the committee to stdout on startup, as a peers file (one `node_id=ip:port,address` line per
+    /// member), which may be passed back via `--peers`.
+    #[arg(long, default_value = "false")]
+    dump_config: bool,
+    /// If set, the address of a SOCKS5 proxy to route the outbound peer connections through.
+    #[arg(long, value_name = "ADDR")]
+    proxy: Option<SocketAddr>,
+    /// If set, the maximum time in ms allowed for a peer connection to complete the handshake (default: 3000).
+    #[arg(long, value_name = "TIMEOUT_MS")]
+    handshake_timeout_ms: Option<u64>,
+    /// If set, the interval in seconds at which to log a compact metrics line.
+    #[arg(long, value_name = "SECS")]
+    metrics_interval_secs: Option<u64>,
+    /// If set, the path to write the metrics of the run to on shutdown, as a JSON object.
+    #[arg(long, value_name = "PATH")]
+    metrics_out: Option<PathBuf>,
+    /// If set, the OTLP/HTTP collector endpoint (e.g. `http://localhost:4318`) to push the metrics to,
+    /// at the metrics interval (defaulting to 10 seconds). Requires the `otlp` feature.
+    #[arg(long, value_name = "URL")]
+    otlp_endpoint: Option<String>,
+    /// If set, the number of seconds without a round advancing, after which the node is considered stalled
+    /// (defaulting to 30 seconds when a stall webhook is given).
+    #[arg(long, value_name = "SECS")]
+    stall_timeout_secs: Option<u64>,
+    /// If set, the URL to post a JSON notification to when the node stalls. Requires the `webhook` feature.
+    #[arg(long, value_name = "URL")]
+    stall_webhook: Option<String>,
+    /// If set, the maximum number of rounds the node may lag behind its peers, before alerting and reporting
+    /// as not ready.
+    #[arg(long, value_name = "ROUNDS")]
+    max_lag_rounds: Option<u64>,
+    /// For testing only: if set, the node simulates a flapping validator, going offline (i.e. disconnecting from all
+    /// of its peers) every given number of seconds, for as many seconds, before reconnecting.
+    #[arg(long, value_name = "SECS", value_parser = clap::value_parser!(u64).range(1..))]
+    flap_interval_secs: Option<u64>,
+    /// The policy for staggering the startup of the node (by default, the node starts without delay).
+    #[arg(long, value_enum, default_value = "none")]
+    stagger_policy: StaggerPolicy,
+    /// If set, wraps batch proposing and signing in a span per round, carrying the round number and node address.
+    #[arg(long, default_value = "false")]
+    trace_rounds: bool,
+    /// If set, watches the peer files for changes, and connects to the added or changed peers.
+    #[arg(long, default_value = "false")]
+    committee_file_watch: bool,
+    /// How the primary materializes the received transmissions.
+    /// Note: The cannons always fire buffers, as their fake payloads do not deserialize; in eager mode,
+    /// the primary still attempts (and times) the deserialization, and keeps the buffers that fail.
+    #[arg(long, value_enum, default_value_t = Materialization::Lazy)]
+    transmission_materialization: Materialization,
+    /// If set, the token guarding the administrative endpoints (e.g. `POST /reset`),
+    /// to be passed as `Authorization: Bearer <token>`. The endpoints are disabled otherwise.
+    #[arg(long, value_name = "TOKEN")]
+    #[cfg(feature = "rest")]
+    admin_token: Option<String>,
+    /// If set, the maximum number of REST requests served concurrently; the excess is rejected with a `503`.
+    #[arg(long, value_name = "N", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    #[cfg(feature = "rest")]
+    rest_max_connections: Option<usize>,
+    /// The base offset of the seeds the accounts are sampled from, yielding a distinct (but deterministic)
+    /// committee per value. Note: All nodes in one cluster must share the same seed base.
+    #[arg(long, value_name = "SEED", default_value_t = 0)]
+    seed_base: u64,
+    /// If set, the hex-encoded secret all committee accounts are derived from (by member index), instead of
+    /// the seed base. Note: All nodes in one cluster must share the same master seed.
+    #[arg(long, value_name = "HEX")]
+    master_seed: Option<String>,
+    /// If set, the committee rotates every given (even) number of rounds, deterministically changing the stakes.
+    #[arg(long, value_name = "ROUNDS")]
+    epoch_length: Option<u64>,
+    /// If set, the directory to checkpoint the proposal cache (i.e. the pending certificates and proposals) to,
+    /// and to restore the newest valid checkpoint from on startup.
+    #[arg(long, value_name = "PATH")]
+    storage_path: Option<PathBuf>,
+    /// If set, the interval in seconds at which to checkpoint the proposal cache to the storage path,
+    /// bounding the state lost on an unclean shutdown to one interval.
+    #[arg(long, value_name = "SECS")]
+    checkpoint_interval_secs: Option<u64>,
+    /// If set, the integrity of the proposal cache loaded on startup (i.e. the signatures, committee membership and
+    /// parent links of the certificates of the restored checkpoint, or of the persisted proposal cache) is verified,
+    /// and the node refuses to start if any check fails.
+    #[arg(long, default_value = "false")]
+    verify_on_load: bool,
+    /// If set, the path to a file containing the interval in ms to fire transmissions at, which is re-read
+    /// every second, and overrides the fire intervals (e.g. to ramp the load up or down during a run).
+    #[arg(long, value_name = "PATH")]
+    #[cfg(feature = "cannons")]
+    fire_control_file: Option<PathBuf>,
+    /// If set, the number of seconds to wait before the cannons start firing.
+    #[arg(long, value_name = "SECS")]
+    #[cfg(feature = "cannons")]
+    fire_warmup_secs: Option<u64>,
+    /// If set, the number of connected peers to wait for before the cannons start firing.
+    /// If combined with `--fire-warmup-secs`, the warm-up starts once the peers are connected.
+    #[arg(long, value_name = "N")]
+    #[cfg(feature = "cannons")]
+    min_peers_to_start: Option<usize>,
+    /// If set, the maximum number of unconfirmed transmissions the primary processes concurrently.
+    /// By default, the parallelism is unbounded. When bounded, transmissions beyond this number queue in the
+    /// primary channel (8192 entries), and the cannons block once it is full.
+    #[arg(long)]
+    primary_workers: Option<usize>,
+    /// If set, the fraction (between 0 and 1) of the committee stake above which a validator is warned about
+    /// at startup.
+    #[arg(long, value_name = "FRACTION")]
+    max_stake_fraction: Option<f64>,
+    /// If set, the fraction (between 0 and 1) of inbound gateway messages to randomly drop, seeded by the node ID.
+    #[arg(long, value_name = "RATE")]
+    drop_inbound_rate: Option<f64>,
+    /// If set, the factor to accelerate the example's delays by (i.e. the cannon intervals, the warm-up, the
+    /// consensus handler and the startup stagger), for simulations: `2.0` halves these delays. The delays of
+    /// the memory pool itself (e.g. the round and ping intervals) remain in wall time.
+    #[arg(long, value_name = "SCALE")]
+    time_scale: Option<f64>,
+    /// The number of worker threads of the tokio runtime (defaults to the number of cores).
+    #[arg(long)]
+    worker_threads: Option<usize>,
+    /// If set, an event is logged each time a peer connects or disconnects.
+    #[arg(long, default_value = "false")]
+    log_connection_events: bool,
+    /// If set, the node does not relay the transmissions it received from its peers (i.e. it acts as a leaf node).
+    #[arg(long, default_value = "false")]
+    no_relay: bool,
+    /// The number of consecutive failures to connect to a peer after which the failures are logged at WARN level
+    /// (the failures before are logged at DEBUG level).
+    #[arg(long, value_name = "N", default_value = "1")]
+    warn_after_failures: u32,
+    /// If set, the number of consecutive failures to connect to a peer after which the failures are logged at ERROR
+    /// level (must not be less than `--warn-after-failures`).
+    #[arg(long, value_name = "M")]
+    error_after_failures: Option<u32>,
+    /// If set, the path to the file to append a line to for every event sent and received by the gateway,
+    /// as `timestamp_micros,direction,peer_ip,event,size_in_bytes` (e.g. for an offline analysis of a run).
+    #[arg(long, value_name = "PATH")]
+    gateway_log: Option<PathBuf>,
+    /// If set, the memory budget (in megabytes) of the storage; the cannons are paused above 90% of it,
+    /// and resumed once the estimated size of the storage drops below 75%.
+    #[arg(long, value_name = "MB", value_parser = clap::value_parser!(u64).range(1..))]
+    max_memory_mb: Option<u64>,
+    /// If set, the path to the file to also write the logs to.
+    #[arg(long, value_name = "PATH")]
+    log_file: Option<PathBuf>,
+    /// The rotation policy of the log file: `never`, `hourly`, `daily`, or `size:<MB>`.
+    #[arg(long, value_name = "POLICY", default_value = "never")]
+    log_rotation: LogRotation,
+    /// If set, the monitoring server is not started.
+    #[arg(long, default_value = "false")]
+    #[cfg(feature = "rest")]
+    no_rest: bool,
+    /// If set, runs a short in-process cluster of 4 BFT nodes and exits with a nonzero code if it fails to progress.
+    /// Note: The `--mode`, `--id`, `--num-nodes` and `--peers` options are ignored in this mode.
+    #[arg(long, default_value = "false")]
+    self_test: bool,
+    /// If set, the address the node's account is expected to have; the node fails to start on a mismatch.
+    #[arg(long, value_name = "ADDRESS")]
+    expect_address: Option<String>,
+}
+
+/// A helper method to parse the peers provided to the CLI.
+/// Each line may also carry the validator's address, as `node_id=ip:port,aleo1...`; the address is validated,
+/// so that a typo surfaces before the node starts. Errors are reported with the offending line number.
+fn parse_peers(peers_string: String) -> Result<HashMap<u16, SocketAddr>, Error> {
+    // Parses one line, of the form `node_id=ip:port[,address]`.
+    fn parse_peer(peer: &str) -> Result<(u16, SocketAddr), Error> {
+        let (peer, address) = match peer.split_once(',') {
+            Some((peer, address)) => (peer, Some(address.trim())),
+            None => (peer, None),
+        };
+        let mut split = peer.split('=');
+        let node_id = u16::from_str(split.next().ok_or_else(|| anyhow!("Bad Format"))?)?;
+        let addr: String = split.next().ok_or_else(|| anyhow!("Bad Format"))?.parse()?;
+        let ip = SocketAddr::from_str(addr.as_str())?;
+        if let Some(address) = address {
+            Address::<CurrentNetwork>::from_str(address).map_err(|e| anyhow!("Invalid address '{address}' - {e}"))?;
+        }
+        Ok((node_id, ip))
+    }
+
+    // Expect list of peers in the form of `node_id=ip:port[,address]`, one per line.
+    let mut peers = HashMap::new();
+    for (index, peer) in peers_string.lines().enumerate() {
+        let (node_id, ip) =
+            parse_peer(peer).map_err(|e| anyhow!("Invalid peer on line {} ('{peer}') - {e}", index + 1))?;
+        peers.insert(node_id, ip);
+    }
+    Ok(peers)
+}
+
+/// Formats the given peers as a peers file, one `node_id=ip:port,address` line per peer (see `parse_peers`).
+fn format_peers(peers: impl IntoIterator<Item = (u16, SocketAddr, Address<CurrentNetwork>)>) -> String {
+    peers.into_iter().map(|(node_id, ip, address)| format!("{node_id}={ip},{address}\n")).collect()
+}
+
+/// The part of the peer subnet address incremented by the node ID.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum SubnetIncrement {
+    /// Node `i` listens on `base_ip + i`, at the base port.
+    Ip,
+    /// Node `i` listens on the base IP, at `base_port + i`.
+    Port,
+}
+
+/// Derives the peers of `num_nodes` nodes from the address of node 0, incrementing its IP or its port by the node ID.
+/// Note: The derived IPs must stay within the `/24` subnet of the base IP, which must be an IPv4 address.
+fn derive_peers(
+    base: SocketAddr,
+    num_nodes: u16,
+    increment: SubnetIncrement,
+) -> Result<HashMap<u16, SocketAddr>, Error> {
+    let mut peers = HashMap::with_capacity(num_nodes as usize);
+    for node_id in 0..num_nodes {
+        let ip = match increment {
+            SubnetIncrement::Ip => {
+                let IpAddr::V4(base_ip) = base.ip() else {
+                    return Err(anyhow!("Incrementing the peer IPs requires an IPv4 base, found '{base}'"));
+                };
+                let [a, b, c, d] = base_ip.octets();
+                let d = u8::try_from(u32::from(d) + u32::from(node_id))
+                    .map_err(|_| anyhow!("The IP of node {node_id} is outside of the subnet {a}.{b}.{c}.0/24"))?;
+                SocketAddr::new(IpAddr::V4(Ipv4Addr::new(a, b, c, d)), base.port())
+            }
+            SubnetIncrement::Port => {
+                let port = base.port().checked_add(node_id);
+                let port = port.ok_or_else(|| anyhow!("The port of node {node_id} is outside of the port range"))?;
+                SocketAddr::new(base.ip(), port)
+            }
+        };
+        peers.insert(node_id, ip);
+    }
+    Ok(peers)
+}
+
+/// A helper method to merge several peer maps into one, erroring on conflicting node IDs.
+fn merge_peers(
+    peer_maps: impl IntoIterator<Item = HashMap<u16, SocketAddr>>,
+) -> Result<HashMap<u16, SocketAddr>, Error> {
+    let mut merged = HashMap::new();
+    for peers in peer_maps {
+        for (node_id, ip) in peers {
+            match merged.insert(node_id, ip) {
+                Some(existing) if existing != ip => {
+                    return Err(anyhow!("Conflicting definitions for node {node_id}: '{existing}' and '{ip}'"));
+                }
+                _ => (),
+            }
+        }
+    }
+    Ok(merged)
+}
+
+/// Returns the given command with its boolean flags made overridable, i.e. each flag may also be passed
+/// with an explicit value (e.g. `--quiet=false`), so that the flags can turn off an option of the config file.
+fn with_overridable_flags(command: Command) -> Command {
+    let flags = command
+        .get_arguments()
+        .filter(|arg| matches!(arg.get_action(), ArgAction::SetTrue))
+        .map(|arg| arg.get_id().clone())
+        .collect::<Vec<_>>();
+    flags.into_iter().fold(command, |command, id| {
+        command.mut_arg(id, |arg| {
+            arg.action(ArgAction::Set)
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("true")
+                .default_value("false")
+        })
+    })
+}
+
+/// Parses the options of a TOML config file into CLI arguments (e.g. `num_nodes = 4` into `--num-nodes=4`),
+/// each paired with the ID of its flag. Note: A boolean flag is passed with its value (e.g. `quiet = true` as
+/// `--quiet=true`), and an array is passed as one argument per element (e.g. for the repeatable `--peers`).
+/// For a flag with an optional value (e.g. `--fire-transmissions`), `true` passes the bare flag.
+fn parse_config_file(contents: &str) -> Result<Vec<(clap::Id, String)>, Error> {
+    let table = toml::from_str::<toml::value::Table>(contents)?;
+    let command = Args::command();
+    let mut args = Vec::new();
+    for (key, value) in table {
+        // Accept the options in both snake case and kebab case.
+        let long = key.replace('_', "-");
+        ensure!(long != "config-file", "A config file cannot reference another config file");
+        let Some(arg) = command.get_arguments().find(|arg| arg.get_long() == Some(long.as_str())) else {
+            bail!("Unknown option '{key}' in the config file");
+        };
+        let is_flag = matches!(arg.get_action(), ArgAction::SetTrue);
+        let values = match value {
+            toml::Value::Array(values) => values,
+            value => vec![value],
+        };
+        for value in values {
+            let arg_string = match value {
+                toml::Value::Boolean(value) if is_flag => format!("--{long}={value}"),
+                toml::Value::Boolean(true) => format!("--{long}"),
+                toml::Value::Boolean(false) => continue,
+                toml::Value::String(value) => format!("--{long}={value}"),
+                toml::Value::Integer(value) => format!("--{long}={value}"),
+                toml::Value::Float(value) => format!("--{long}={value}"),
+                value => bail!("Unsupported value for option '{key}' in the config file: {value}"),
+            };
+            args.push((arg.get_id().clone(), arg_string));
+        }
+    }
+    Ok(args)
+}
+
+/// Parses the CLI arguments, along with the options of the `--config-file` (if any).
+/// The flags take precedence over the options of the file, including the repeatable flags (e.g. `--peers`).
+fn parse_args(args: Vec<OsString>) -> Result<Args, Error> {
+    let parse = |args: Vec<OsString>| -> Result<Args, Error> {
+        let matches = with_overridable_flags(Args::command()).try_get_matches_from(args)?;
+        Ok(Args::from_arg_matches(&matches)?)
+    };
+    // Parse the flags alone first (with none of them required), to find the config file and the flags passed.
+    // Note: If this fails (e.g. for `--help`), the full parse reports the error, along with the required flags.
+    let Ok(cli) = with_overridable_flags(Args::command_for_update()).try_get_matches_from(&args) else {
+        return parse(args);
+    };
+    let Some(path) = cli.get_one::<PathBuf>("config_file") else {
+        return parse(args);
+    };
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Failed to read the config file '{}' - {e}", path.display()))?;
+    let options =
+        parse_config_file(&contents).map_err(|e| anyhow!("Invalid config file '{}' - {e}", path.display()))?;
+    // Insert the options of the file before the flags, skipping the options overridden by the flags.
+    let file_args = options
+        .into_iter()
+        .filter(|(id, _)| cli.value_source(id.as_str()) != Some(ValueSource::CommandLine))
+        .map(|(_, arg)| OsString::from(arg));
+    let (binary, flags) = args.split_at(args.len().min(1));
+    parse(binary.iter().cloned().chain(file_args).chain(flags.iter().cloned()).collect())
+}
+
+/**************************************************************************************************/
+
+fn main() -> Result<()> {
+    let args = match parse_args(std::env::args_os().collect()) {
+        Ok(args) => args,
+        // Let clap report its own errors (e.g. for `--help`), with the appropriate output and exit code.
+        Err(error) => match error.downcast::<clap::Error>() {
+            Ok(error) => error.exit(),
+            Err(error) => return Err(error),
+        },
+    };
+
+    initialize_logger(1, args.log_file.clone().map(|path| (path, args.log_rotation)));
+
+    // Initialize the runtime, with one worker thread per core by default.
+    let worker_threads = match args.worker_threads {
+        Some(worker_threads) => worker_threads,
+        None => std::thread::available_parallelism().map_or(1, |num_cores| num_cores.get()),
+    };
+    ensure!(worker_threads > 0, "The number of worker threads must be greater than zero");
+    // If a time scale was provided, accelerate the example's delays by it.
+    if let Some(time_scale) = args.time_scale {
+        set_time_scale(time_scale)?;
+    }
+    SHUTDOWN_ON_PANIC.store(args.shutdown_on_panic, Ordering::Relaxed);
+    loop {
+        // Note: Each run of the node gets its own runtime, so that a reset aborts all of the node's tasks.
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .worker_threads(worker_threads)
+            .build()
+            .map_err(|e| anyhow!("Failed to initialize the runtime - {e}"))?;
+        let is_reset = runtime.block_on(run(args.clone()))?;
+        runtime.shutdown_timeout(std::time::Duration::from_secs(5));
+        if !is_reset {
+            return Ok(());
+        }
+        info!("Restarting the node from round 0...");
+    }
+}
+
+/// Runs the node with the given arguments.
+/// Returns `true` if the node was reset, and should be restarted.
+async fn run(args: Args) -> Result<bool> {
+    // If requested, run the self-test instead of the node.
+    if args.self_test {
+        return run_self_test(&args).await.map(|_| false);
+    }
+
+    let mut peer_maps =
+        args.peers.iter().map(|path| parse_peers(std::fs::read_to_string(path)?)).collect::<Result<Vec<_>>>()?;
+    // If a peer subnet was provided, derive the peers from it.
+    if let Some(base) = args.peer_subnet {
+        peer_maps.push(derive_peers(base, args.num_nodes, args.peer_subnet_increment)?);
+    }
+    let peers = merge_peers(peer_maps)?;
+    let initial_peers = peers.clone();
+    // Determine the dev mode, which locates the proposal cache.
+    #[cfg(feature = "rest")]
+    let dev = if peers.contains_key(&args.id) { None } else { Some(args.id) };
+
+    // Stagger the startup of the node.
+    sleep_scaled(args.stagger_policy.delay(args.id)).await;
+
+    // Initialize an optional BFT holder.
+    let mut bft_holder = None;
+
+    // Start the node.
+    let (primary, sender) = match args.mode {
+        Mode::Bft => {
+            // Start the BFT.
+            let (bft, sender) = start_bft(&args, peers).await?;
+            // Set the BFT holder.
+            bft_holder = Some(bft.clone());
+            // Return the primary and sender.
+            (bft.primary().clone(), sender)
+        }
+        Mode::Narwhal => start_primary(&args, peers).await?,
+    };
+
+    // Handle OS signals, exporting the metrics of the run on shutdown (if requested).
+    let metrics_out = args.metrics_out.clone().map(|path| (path, std::time::Instant::now(), primary.current_round()));
+    handle_signals(vec![(args.id, primary.clone())], metrics_out);
+
+    // If requested, wrap each round in a span for distributed tracing.
+    if args.trace_rounds {
+        primary.set_round_spans(true);
+    }
+
+    // If a round interval was provided, pace the primary's proposals to it.
+    if let Some(interval_ms) = args.round_interval_ms {
+        primary.set_round_interval_in_ms(interval_ms);
+    }
+
+    // If a certificate request batch size was provided, request the missing certificates in batches of it.
+    if let Some(batch_size) = args.cert_request_batch {
+        primary.set_certificate_request_batch_size(batch_size as usize);
+    }
+
+    // If a straggler delay was provided, inject it into the primary.
+    if let Some(delay_ms) = args.straggler_delay_ms {
+        warn!("Node {} is simulating a straggler with a {delay_ms} ms delay", args.id);
+        primary.set_straggler_delay_in_ms(delay_ms);
+    }
+
+    // If a clock skew was provided, offset the timestamps of the primary's batches by it.
+    if let Some(skew_ms) = args.clock_skew_ms {
+        warn!("Node {} is simulating a clock skew of {skew_ms} ms on its batch timestamps (test-only)", args.id);
+        primary.set_clock_skew_in_ms(skew_ms);
+    }
+
+    // If a maximum round was provided, stop proposing for the rounds past it.
+    if let Some(max_round) = args.max_propose_round {
+        primary.set_max_propose_round(max_round);
+    }
+
+    // Start the cannons, once the warm-up (if any) is over.
+    #[cfg(feature = "cannons")]
+    {
+        ensure!((0.0..=1.0).contains(&args.duplicate_rate), "The duplicate rate must be between 0 and 1");
+        cannons::spawn_cannons(&args, &primary, sender.clone());
+    }
+
+    // Initialize the metrics.
+    #[cfg(feature = "metrics")]
+    if args.metrics {
+        info!("Initializing metrics...");
+        metrics::initialize_metrics(SocketAddr::from_str(&format!("0.0.0.0:{}", 9000 + args.id)).ok());
+    }
+
+    // If requested, watch the peer files for changes.
+    if args.committee_file_watch {
+        ensure!(!args.peers.is_empty(), "'--committee-file-watch' requires at least one '--peers' file");
+        watch_peer_files(primary.clone(), args.clone(), initial_peers);
+    }
+
+    // If the BFT is enabled, watch for changes to the leaders of the committed rounds.
+    if let Some(bft) = &bft_holder {
+        watch_committed_leaders(bft.clone());
+    }
+
+    // If the committee rotates, watch the epoch transitions.
+    if let Some(epoch_length) = args.epoch_length {
+        watch_epochs(primary.clone(), epoch_length);
+    }
+
+    // If a maximum lag was provided, watch the gap between the node's round and its peers'.
+    if let Some(max_lag_rounds) = args.max_lag_rounds {
+        watch_round_lag(primary.clone(), max_lag_rounds);
+    }
+
+    // If a flap interval was provided, take the node offline and back online repeatedly.
+    if let Some(interval_secs) = args.flap_interval_secs {
+        warn!("Node {} is simulating a flapping validator every {interval_secs} seconds (test-only)", args.id);
+        flap_connections(primary.clone(), interval_secs);
+    }
+
+    // If a stall timeout or webhook was provided, watch for the node's round to stop advancing.
+    if args.stall_timeout_secs.is_some() || args.stall_webhook.is_some() {
+        #[cfg(not(feature = "webhook"))]
+        if let Some(url) = &args.stall_webhook {
+            return Err(anyhow!("Notifying the webhook at '{url}' requires the 'webhook' feature"));
+        }
+        let timeout_secs = args.stall_timeout_secs.unwrap_or(30);
+        watch_stalls(primary.clone(), args.id, timeout_secs, args.stall_webhook.clone());
+    }
+
+    // If a memory budget was provided, shed load as the storage approaches it.
+    if let Some(max_memory_mb) = args.max_memory_mb {
+        watch_memory_budget(primary.clone(), max_memory_mb);
+    }
+
+    // If a checkpoint interval was provided, periodically checkpoint the proposal cache to the storage path.
+    if let Some(interval_secs) = args.checkpoint_interval_secs {
+        let Some(storage_path) = args.storage_path.clone() else {
+            return Err(anyhow!("'--checkpoint-interval-secs' requires '--storage-path'"));
+        };
+        std::fs::create_dir_all(&storage_path)?;
+        checkpoint_periodically(primary.clone(), storage_path, args.id, interval_secs);
+    }
+
+    // If a metrics interval was provided, periodically log a compact metrics line.
+    if let Some(interval_secs) = args.metrics_interval_secs {
+        log_metrics_periodically(primary.clone(), interval_secs);
+    }
+
+    // If an OTLP endpoint was provided, periodically push the metrics to it.
+    if let Some(endpoint) = args.otlp_endpoint.clone() {
+        #[cfg(feature = "otlp")]
+        export_otlp_metrics(primary.clone(), args.id, endpoint, args.metrics_interval_secs.unwrap_or(10));
+        #[cfg(not(feature = "otlp"))]
+        return Err(anyhow!("Exporting the metrics to '{endpoint}' requires the 'otlp' feature"));
+    }
+
+    // Serve the monitoring server, until the node is reset (if requested).
+    #[cfg(feature = "rest")]
+    {
+        rest::serve_until_reset(&args, bft_holder, primary, sender, dev).await
+    }
+    // Without the monitoring server, keep the node alive.
+    #[cfg(not(feature = "rest"))]
+    {
+        drop(sender);
+        std::future::pending::<()>().await;
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale_duration_by_ok() {
+        let duration = std::time::Duration::from_millis(1000);
+        assert_eq!(scale_duration_by(duration, 1.0), duration);
+        assert_eq!(scale_duration_by(duration, 2.0), std::time::Duration::from_millis(500));
+        assert_eq!(scale_duration_by(duration, 0.5), std::time::Duration::from_millis(2000));
+    }
+
+    #[test]
+    fn set_time_scale_rejects_invalid_scales() {
+        for scale in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(set_time_scale(scale).is_err());
+        }
+    }
+
+    #[test]
+    fn parse_peers_empty() -> Result<(), Error> {
+        let peers = parse_peers("".to_owned())?;
+        assert_eq!(peers.len(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_peers_ok() -> Result<(), Error> {
+        let s = r#"0=192.168.1.176:5000
+1=192.168.1.176:5001
+2=192.168.1.176:5002
+3=192.168.1.176:5003"#;
+        let peers = parse_peers(s.to_owned())?;
+        assert_eq!(peers.len(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_peers_with_addresses() -> Result<(), Error> {
+        let address = Account::<CurrentNetwork>::new(&mut TestRng::default())?.address();
+        let s = format!("0=192.168.1.176:5000,{address}\n1=192.168.1.176:5001");
+        let peers = parse_peers(s)?;
+        assert_eq!(peers.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn format_peers_round_trip() -> Result<(), Error> {
+        let rng = &mut TestRng::default();
+        let mut members = Vec::new();
+        for i in 0..4 {
+            let ip = SocketAddr::from((Ipv4Addr::LOCALHOST, MEMORY_POOL_PORT + i));
+            members.push((i, ip, Account::<CurrentNetwork>::new(rng)?.address()));
+        }
+        let peers = parse_peers(format_peers(members.clone()))?;
+        assert_eq!(peers, members.iter().map(|(i, ip, _)| (*i, *ip)).collect::<HashMap<_, _>>());
+        Ok(())
+    }
+
+    #[test]
+    fn parse_peers_bad_address() -> Result<(), Error> {
+        let s = "0=192.168.1.176:5000\n1=192.168.1.176:5001,aleo1foo";
+        let error = parse_peers(s.to_owned()).unwrap_err();
+        assert!(error.to_string().contains("line 2"));
+        Ok(())
+    }
+
+    #[test]
+    fn derive_peers_from_subnet() -> Result<(), Error> {
+        let base = SocketAddr::from_str("10.0.0.250:5000")?;
+        // Increment the IPs.
+        let peers = derive_peers(base, 4, SubnetIncrement::Ip)?;
+        assert_eq!(peers.len(), 4);
+        assert_eq!(peers[&0], base);
+        assert_eq!(peers[&3], SocketAddr::from_str("10.0.0.253:5000")?);
+        // Increment the ports.
+        let peers = derive_peers(base, 4, SubnetIncrement::Port)?;
+        assert_eq!(peers[&3], SocketAddr::from_str("10.0.0.250:5003")?);
+        Ok(())
+    }
+
+    #[test]
+    fn derive_peers_outside_of_subnet() -> Result<(), Error> {
+        let base = SocketAddr::from_str("10.0.0.250:65534")?;
+        assert!(derive_peers(base, 7, SubnetIncrement::Ip).is_err());
+        assert!(derive_peers(base, 3, SubnetIncrement::Port).is_err());
+        assert!(derive_peers(SocketAddr::from_str("[::1]:5000")?, 2, SubnetIncrement::Ip).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn parse_peers_bad_id() -> Result<(), Error> {
+        let s = "A=192.168.1.176:5000";
+        let peers = parse_peers(s.to_owned());
+        assert!(peers.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn parse_peers_bad_format() -> Result<(), Error> {
+        let s = "foo";
+        let peers = parse_peers(s.to_owned());
+        assert!(peers.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn parse_log_rotation() {
+        assert_eq!(LogRotation::from_str("never"), Ok(LogRotation::Never));
+        assert_eq!(LogRotation::from_str("hourly"), Ok(LogRotation::Hourly));
+        assert_eq!(LogRotation::from_str("daily"), Ok(LogRotation::Daily));
+        assert_eq!(LogRotation::from_str("size:64"), Ok(LogRotation::Size(64)));
+        assert!(LogRotation::from_str("size:0").is_err());
+        assert!(LogRotation::from_str("size:foo").is_err());
+        assert!(LogRotation::from_str("weekly").is_err());
+    }
+
+    #[test]
+    fn parse_master_seed_ok() -> Result<(), Error> {
+        assert_eq!(parse_master_seed("00ff10")?, vec![0x00, 0xff, 0x10]);
+        assert_eq!(parse_master_seed("0xABcd")?, vec![0xab, 0xcd]);
+        assert!(parse_master_seed("").is_err());
+        assert!(parse_master_seed("0x").is_err());
+        assert!(parse_master_seed("abc").is_err());
+        assert!(parse_master_seed("zz").is_err());
+        assert!(parse_master_seed("+f").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn sample_account_from_master_seed() -> Result<(), Error> {
+        let (master_seed, other_seed) = (parse_master_seed("c0ffee")?, parse_master_seed("c0ffef")?);
+        // Ensure the derivation is deterministic.
+        let account = sample_account(Some(&master_seed), 0, 1)?;
+        assert_eq!(account.address(), sample_account(Some(&master_seed), 0, 1)?.address());
+        // Ensure the seed base is ignored, and the index and master seed change the account.
+        assert_eq!(account.address(), sample_account(Some(&master_seed), 7, 1)?.address());
+        assert_ne!(account.address(), sample_account(Some(&master_seed), 0, 2)?.address());
+        assert_ne!(account.address(), sample_account(Some(&other_seed), 0, 1)?.address());
+        assert_ne!(account.address(), sample_account(None, 0, 1)?.address());
+        Ok(())
+    }
+
+    #[test]
+    fn parse_config_file_ok() -> Result<(), Error> {
+        let contents = "mode = \"bft\"\nnum-nodes = 4\nquiet = true\nmetrics = false\npeers = [\"a.txt\", \"b.txt\"]";
+        let mut args = parse_config_file(contents)?.into_iter().map(|(_, arg)| arg).collect::<Vec<_>>();
+        args.sort();
+        let expected =
+            ["--metrics=false", "--mode=bft", "--num-nodes=4", "--peers=a.txt", "--peers=b.txt", "--quiet=true"];
+        assert_eq!(args, expected);
+        assert!(parse_config_file("unknown_option = 1").is_err());
+        assert!(parse_config_file("config_file = \"other.toml\"").is_err());
+        assert!(parse_config_file("num_nodes = ").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn parse_args_with_config_file() -> Result<(), Error> {
+        let path = std::env::temp_dir().join(format!("simple_node_config_{}.toml", std::process::id()));
+        std::fs::write(&path, "mode = \"bft\"\nid = 1\nnum_nodes = 4\nquiet = true\npeers = [\"a.txt\"]")?;
+        let args = |flags: &[&str]| {
+            let mut args = vec![OsString::from("simple_node"), OsString::from("--config-file"), path.clone().into()];
+            args.extend(flags.iter().map(OsString::from));
+            parse_args(args)
+        };
+
+        // Ensure the options of the file are used, if no flags override them.
+        let file_only = args(&[])?;
+        assert!(matches!(file_only.mode, Mode::Bft));
+        assert_eq!((file_only.id, file_only.num_nodes), (1, 4));
+        assert!(file_only.quiet);
+        assert_eq!(file_only.peers, [PathBuf::from("a.txt")]);
+        // Ensure the flags take precedence over the file, including the repeatable and boolean flags.
+        let overridden = args(&["--id", "2", "--mode", "narwhal", "--peers", "b.txt", "--quiet=false"])?;
+        assert!(matches!(overridden.mode, Mode::Narwhal));
+        assert_eq!((overridden.id, overridden.num_nodes), (2, 4));
+        assert_eq!(overridden.peers, [PathBuf::from("b.txt")]);
+        assert!(!overridden.quiet);
+        // Ensure the path to the config file may also be passed with an equals sign.
+        let config_file = format!("--config-file={}", path.display());
+        let with_equals = parse_args(vec![OsString::from("simple_node"), OsString::from(config_file)])?;
+        assert_eq!((with_equals.id, with_equals.num_nodes), (1, 4));
+        // Ensure the flags are still validated, and may not be repeated unless repeatable.
+        assert!(args(&["--id", "not-a-number"]).is_err());
+        assert!(args(&["--id", "2", "--id", "3"]).is_err());
+
+        std::fs::remove_file(&path)?;
+        // Ensure a missing config file is reported.
+        assert!(args(&[]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn parse_args_with_boolean_flags() -> Result<(), Error> {
+        let args = |flags: &[&str]| {
+            let mut args =
+                ["simple_node", "--mode", "bft", "--id", "0", "--num-nodes", "4"].map(OsString::from).to_vec();
+            args.extend(flags.iter().map(OsString::from));
+            parse_args(args)
+        };
+        // Ensure a boolean flag is off by default, and may be passed bare or with an explicit value.
+        assert!(!args(&[])?.quiet);
+        assert!(args(&["--quiet"])?.quiet);
+        assert!(args(&["--quiet=true"])?.quiet);
+        assert!(!args(&["--quiet=false"])?.quiet);
+        assert!(args(&["--quiet=maybe"]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn merge_peers_ok() -> Result<(), Error> {
+        let a = parse_peers("0=192.168.1.176:5000\n1=192.168.1.176:5001".to_owned())?;
+        let b = parse_peers("1=192.168.1.176:5001\n2=192.168.1.176:5002".to_owned())?;
+        let peers = merge_peers([a, b])?;
+        assert_eq!(peers.len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn merge_peers_conflict() -> Result<(), Error> {
+        let a = parse_peers("0=192.168.1.176:5000".to_owned())?;
+        let b = parse_peers("0=192.168.1.177:5000".to_owned())?;
+        assert!(merge_peers([a, b]).is_err());
+        Ok(())
+    }
+}
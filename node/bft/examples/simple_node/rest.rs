@@ -0,0 +1,1230 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The monitoring server of the node, serving the REST API.
+
+use super::{
+    Args,
+    CurrentNetwork,
+    EPOCH_LENGTH,
+    IS_LAGGING,
+    IS_OFFLINE,
+    count_participation,
+    estimate_storage_size,
+    network_round,
+    remove_checkpoints,
+    spawn_task,
+    transmission_num_bytes,
+};
+
+use snarkos_node_bft::{
+    BFT,
+    LeaderState,
+    Primary,
+    helpers::{PrimarySender, now_in_millis, proposal_cache_path},
+};
+use snarkvm::{
+    console::types::Address,
+    ledger::{
+        narwhal::{BatchHeader, Data, TransmissionID},
+        puzzle::SolutionID,
+    },
+    prelude::{Field, Network, ToBytes, Uniform},
+};
+
+use ::bytes::Bytes;
+use anyhow::{Result, anyhow};
+use axum::{
+    BoxError,
+    Router,
+    body::Body,
+    error_handling::HandleErrorLayer,
+    extract::{Path, Query, State},
+    http::{HeaderMap, HeaderName, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use axum_extra::response::ErasedJson;
+use indexmap::IndexMap;
+use rand::Rng;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    net::SocketAddr,
+    str::FromStr,
+    sync::{Arc, Mutex, atomic::Ordering},
+};
+use tokio::{
+    net::TcpListener,
+    sync::{mpsc, oneshot},
+};
+use tokio_stream::StreamExt;
+use tower::{ServiceBuilder, limit::GlobalConcurrencyLimitLayer, load_shed::LoadShedLayer};
+
+/// The maximum size in bytes of a transmission served by `/transmission/:id/raw`.
+const MAX_RAW_TRANSMISSION_BYTES: usize = 8 * 1024 * 1024; // 8 MiB
+
+/// The maximum number of certificates visited by `/round/:round/ancestry`.
+const MAX_ANCESTRY_CERTIFICATES: usize = 1 << 16;
+
+/// The number of samples retained by `/bft/gap-history` (one per second).
+const GAP_HISTORY_LEN: usize = 300;
+
+/// A sample of `(UNIX timestamp in ms, proposing round, committed round)`.
+type GapSample = (i64, u64, u64);
+
+/// The number of samples of the lag behind the peers retained for `/synced` (one per second).
+const LAG_HISTORY_LEN: usize = 10;
+
+/// An enum of error handlers for the REST API server.
+pub struct RestError(pub String);
+
+impl IntoResponse for RestError {
+    fn into_response(self) -> Response {
+        (StatusCode::INTERNAL_SERVER_ERROR, format!("Something went wrong: {}", self.0)).into_response()
+    }
+}
+
+impl From<anyhow::Error> for RestError {
+    fn from(err: anyhow::Error) -> Self {
+        Self(err.to_string())
+    }
+}
+
+#[derive(Clone)]
+struct NodeState {
+    bft: Option<BFT<CurrentNetwork>>,
+    primary: Primary<CurrentNetwork>,
+    /// The sender of the unconfirmed transmissions to the primary.
+    sender: PrimarySender<CurrentNetwork>,
+    /// The token guarding the administrative endpoints, if they are enabled.
+    admin_token: Option<String>,
+    /// The sender used to request a reset of the node.
+    reset_sender: mpsc::Sender<()>,
+    /// The recent samples of the proposing and committed rounds, oldest first.
+    gap_history: Arc<Mutex<VecDeque<GapSample>>>,
+    /// The recent samples of the node's lag (in rounds) behind its peers, oldest first.
+    lag_history: Arc<Mutex<VecDeque<u64>>>,
+}
+
+/// Samples the node's lag (in rounds) behind the highest round observed among its peers once per second.
+fn sample_round_lag(primary: Primary<CurrentNetwork>, lag_history: Arc<Mutex<VecDeque<u64>>>) {
+    spawn_task("lag sampler", async move {
+        loop {
+            let (current_round, network_round) = network_round(&primary);
+            {
+                let mut lag_history = lag_history.lock().unwrap();
+                if lag_history.len() == LAG_HISTORY_LEN {
+                    lag_history.pop_front();
+                }
+                lag_history.push_back(network_round.saturating_sub(current_round));
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        }
+    });
+}
+
+/// Samples the node's proposing round and the BFT's committed round once per second, into the bounded history.
+fn sample_round_gap(bft: BFT<CurrentNetwork>, gap_history: Arc<Mutex<VecDeque<GapSample>>>) {
+    spawn_task("gap sampler", async move {
+        loop {
+            let sample = (now_in_millis(), bft.primary().proposing_round(), bft.last_committed_round());
+            {
+                let mut gap_history = gap_history.lock().unwrap();
+                if gap_history.len() == GAP_HISTORY_LEN {
+                    gap_history.pop_front();
+                }
+                gap_history.push_back(sample);
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        }
+    });
+}
+
+/// Returns the error response, if the request does not carry the admin token, as `Authorization: Bearer <token>`.
+fn check_admin(node: &NodeState, headers: &HeaderMap) -> Option<Response> {
+    let Some(admin_token) = &node.admin_token else {
+        return Some((StatusCode::FORBIDDEN, "The admin endpoints are disabled (see '--admin-token')").into_response());
+    };
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if token == Some(admin_token.as_str()) {
+        None
+    } else {
+        Some((StatusCode::UNAUTHORIZED, "Invalid admin token").into_response())
+    }
+}
+
+/// Resets the node, clearing its storage and restarting consensus from round 0, without restarting the process.
+/// Note: The peer files are re-read on restart; this endpoint requires the admin token.
+async fn post_reset(State(node): State<NodeState>, headers: HeaderMap) -> Response {
+    if let Some(response) = check_admin(&node, &headers) {
+        return response;
+    }
+    match node.reset_sender.try_send(()) {
+        Ok(()) => {
+            warn!("Resetting the node (requested via REST)...");
+            ErasedJson::pretty(json!({ "reset": true })).into_response()
+        }
+        Err(_) => (StatusCode::CONFLICT, "A reset is already in progress").into_response(),
+    }
+}
+
+/// Returns the leader of the previous round, if one was present.
+async fn get_leader(State(node): State<NodeState>) -> Result<ErasedJson, RestError> {
+    match &node.bft {
+        Some(bft) => Ok(ErasedJson::pretty(bft.leader())),
+        None => Err(RestError::from(anyhow!("BFT is not enabled"))),
+    }
+}
+
+/// Returns the inputs of the leader election for the given round: the committee (lookback) used for the round, and
+/// the seed of the election, i.e. the committee's starting round, the round, and the committee's total stake.
+/// Note: The leader is the member whose stake range contains the seed's hash modulo the total stake, so comparing
+/// these inputs across nodes pinpoints whether a disagreement on the leader stems from the committee or the round.
+async fn get_leader_inputs(State(node): State<NodeState>, Path(round): Path<u64>) -> Result<ErasedJson, RestError> {
+    let committee = node.primary.ledger().get_committee_lookback_for_round(round)?;
+    let committee_hash = format!("{:x}", Sha256::digest(committee.to_bytes_le()?));
+    // Construct the members, in the order of the committee.
+    let members = committee
+        .members()
+        .iter()
+        .map(|(address, (stake, ..))| (address.to_string(), json!(stake)))
+        .collect::<serde_json::Map<_, _>>();
+    Ok(ErasedJson::pretty(json!({
+        "round": round,
+        "committee_id": committee.id().to_string(),
+        "committee_hash": committee_hash,
+        "starting_round": committee.starting_round(),
+        "total_stake": committee.total_stake(),
+        "seed": [committee.starting_round(), round, committee.total_stake()],
+        "members": members,
+        "leader": committee.get_leader(round)?,
+    })))
+}
+
+/// Returns `true` if the node stores the certificate of the leader of the given (even) round.
+/// Note: This is the lightweight check for whether a round can commit, e.g. for a client polling for finality.
+async fn get_leader_present(State(node): State<NodeState>, Path(round): Path<u64>) -> Result<Response, RestError> {
+    if round % 2 != 0 {
+        let message = format!("Round {round} is odd, and has no leader (only even rounds have a leader)");
+        return Ok((StatusCode::BAD_REQUEST, message).into_response());
+    }
+    let leader = node.primary.ledger().get_committee_lookback_for_round(round)?.get_leader(round)?;
+    Ok(ErasedJson::new(node.primary.storage().contains_certificate_in_round_from(round, leader)).into_response())
+}
+
+/// Returns the current committee, with each member's stake fraction, and the quorum and availability fractions.
+async fn get_committee(State(node): State<NodeState>) -> Result<ErasedJson, RestError> {
+    let committee = node.primary.ledger().current_committee()?;
+    // Compute the fraction of the total stake for the given stake.
+    let total_stake = committee.total_stake();
+    let fraction = |stake: u64| match total_stake {
+        0 => 0.0,
+        total => stake as f64 / total as f64,
+    };
+    // Construct the members.
+    let members = committee
+        .members()
+        .iter()
+        .map(|(address, (stake, is_open, commission))| {
+            let member = json!({
+                "stake": stake,
+                "fraction": fraction(*stake),
+                "is_open": is_open,
+                "commission": commission,
+            });
+            (address.to_string(), member)
+        })
+        .collect::<serde_json::Map<_, _>>();
+    Ok(ErasedJson::pretty(json!({
+        "id": committee.id().to_string(),
+        "starting_round": committee.starting_round(),
+        "total_stake": total_stake,
+        "quorum_threshold": committee.quorum_threshold(),
+        "quorum_fraction": fraction(committee.quorum_threshold()),
+        "availability_threshold": committee.availability_threshold(),
+        "availability_fraction": fraction(committee.availability_threshold()),
+        "members": members,
+    })))
+}
+
+/// Returns the current committee in its byte serialization, with its SHA-256 hash in the `x-committee-hash` header.
+/// Note: Unlike the JSON view, the bytes are exact, so nodes share the same committee iff their hashes are equal.
+async fn get_committee_raw(State(node): State<NodeState>) -> Result<Response, RestError> {
+    let bytes = node.primary.ledger().current_committee()?.to_bytes_le()?;
+    let hash = format!("{:x}", Sha256::digest(&bytes));
+    let headers = [
+        (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+        (HeaderName::from_static("x-committee-hash"), hash),
+    ];
+    Ok((headers, bytes).into_response())
+}
+
+/// Returns the upcoming committee transitions, i.e. for the next `count` epochs (default 4, at most 64), the round
+/// the epoch starts at and the resulting committee, with each member's stake and the committee's SHA-256 hash.
+/// Note: Without an epoch length (`--epoch-length`), the committee never changes, so the schedule is empty.
+async fn get_committee_schedule(
+    State(node): State<NodeState>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<ErasedJson, RestError> {
+    // The default and maximum number of upcoming transitions to return.
+    const DEFAULT_COUNT: u64 = 4;
+    const MAX_COUNT: u64 = 64;
+
+    let count = match params.get("count") {
+        Some(count) => u64::from_str(count).map_err(|e| anyhow!("Invalid count '{count}' - {e}"))?,
+        None => DEFAULT_COUNT,
+    };
+    let Some(epoch_length) = EPOCH_LENGTH.get().copied() else {
+        return Ok(ErasedJson::pretty(json!({ "epoch_length": null, "epoch": null, "transitions": [] })));
+    };
+    let epoch = node.primary.current_round() / epoch_length;
+    // Construct the transitions of the upcoming epochs.
+    let mut transitions = Vec::new();
+    for next_epoch in (epoch + 1)..=(epoch + count.min(MAX_COUNT)) {
+        let round = next_epoch * epoch_length;
+        let committee = node.primary.ledger().get_committee_for_round(round)?;
+        let members = committee
+            .members()
+            .iter()
+            .map(|(address, (stake, ..))| (address.to_string(), json!(stake)))
+            .collect::<serde_json::Map<_, _>>();
+        transitions.push(json!({
+            "epoch": next_epoch,
+            "round": round,
+            "committee_id": committee.id().to_string(),
+            "committee_hash": format!("{:x}", Sha256::digest(committee.to_bytes_le()?)),
+            "total_stake": committee.total_stake(),
+            "members": members,
+        }));
+    }
+    Ok(ErasedJson::pretty(json!({ "epoch_length": epoch_length, "epoch": epoch, "transitions": transitions })))
+}
+
+/// Returns `200 OK` if the node is ready, and `503 Service Unavailable` if it lags behind its peers.
+async fn get_ready() -> Response {
+    if IS_LAGGING.load(Ordering::Relaxed) {
+        (StatusCode::SERVICE_UNAVAILABLE, "lagging").into_response()
+    } else {
+        (StatusCode::OK, "ready").into_response()
+    }
+}
+
+/// Returns whether the node is synced to the network, i.e. its lag behind the highest round observed among
+/// its peers is within `threshold` rounds (default 2), and did not grow over the last samples.
+async fn get_synced(
+    State(node): State<NodeState>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<ErasedJson, RestError> {
+    // The default number of rounds the node may lag behind its peers, and still be synced.
+    const DEFAULT_THRESHOLD: u64 = 2;
+
+    let threshold = match params.get("threshold") {
+        Some(threshold) => u64::from_str(threshold).map_err(|e| anyhow!("Invalid threshold '{threshold}' - {e}"))?,
+        None => DEFAULT_THRESHOLD,
+    };
+    let (current_round, network_round) = network_round(&node.primary);
+    let lag = network_round.saturating_sub(current_round);
+    // Determine whether the lag grew over the sampled window.
+    let is_growing = {
+        let lag_history = node.lag_history.lock().unwrap();
+        matches!((lag_history.front(), lag_history.back()), (Some(first), Some(last)) if last > first)
+    };
+    Ok(ErasedJson::pretty(json!({
+        "synced": lag <= threshold && !is_growing,
+        "lag": lag,
+        "threshold": threshold,
+        "is_growing": is_growing,
+    })))
+}
+
+/// Returns the status of the node, distinguishing the round it is proposing for from the round it has committed.
+/// Note: The committed round is only available in BFT mode.
+async fn get_status(State(node): State<NodeState>) -> Result<ErasedJson, RestError> {
+    let (num_materialized, num_failed, time_in_micros) = node.primary.materialization_stats();
+    Ok(ErasedJson::pretty(json!({
+        "current_round": node.primary.current_round(),
+        "proposing_round": node.primary.proposing_round(),
+        "committed_round": node.bft.as_ref().map(|bft| bft.last_committed_round()),
+        "epoch": EPOCH_LENGTH.get().map(|epoch_length| node.primary.current_round() / epoch_length),
+        "is_synced": node.primary.is_synced(),
+        "connected_peers": node.primary.gateway().number_of_connected_peers(),
+        "is_offline": IS_OFFLINE.load(Ordering::Relaxed),
+        "materialization": {
+            "materialized": num_materialized,
+            "failed": num_failed,
+            "time_micros": time_in_micros,
+        },
+    })))
+}
+
+/// Returns, for each committee member, whether it authored a certificate within the last `window` rounds
+/// (default 10), along with the fraction of the stake that is live, and whether it reaches the quorum threshold.
+async fn get_liveness(
+    State(node): State<NodeState>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<ErasedJson, RestError> {
+    // The default number of rounds within which a member must have certified to be live.
+    const DEFAULT_WINDOW: u64 = 10;
+
+    let window = match params.get("window") {
+        Some(window) => u64::from_str(window).map_err(|e| anyhow!("Invalid window '{window}' - {e}"))?,
+        None => DEFAULT_WINDOW,
+    };
+    let current_round = node.primary.current_round();
+    let committee = node.primary.ledger().get_committee_lookback_for_round(current_round)?;
+    // Determine the liveness of each member.
+    let mut live_stake = 0u64;
+    let members = committee
+        .members()
+        .iter()
+        .map(|(address, (stake, _, _))| {
+            let last_round = node.primary.storage().get_certificate_ids_for_author(*address).last().map(|(r, _)| *r);
+            let is_live = last_round.map_or(false, |round| current_round.saturating_sub(round) <= window);
+            if is_live {
+                live_stake += stake;
+            }
+            let member = json!({ "stake": stake, "last_certified_round": last_round, "is_live": is_live });
+            (address.to_string(), member)
+        })
+        .collect::<serde_json::Map<_, _>>();
+    let live_stake_fraction = match committee.total_stake() {
+        0 => 0.0,
+        total => live_stake as f64 / total as f64,
+    };
+    Ok(ErasedJson::pretty(json!({
+        "round": current_round,
+        "window": window,
+        "live_stake": live_stake,
+        "live_stake_fraction": live_stake_fraction,
+        "quorum_threshold": committee.quorum_threshold(),
+        "has_quorum": live_stake >= committee.quorum_threshold(),
+        "members": members,
+    })))
+}
+
+/// Returns the configured and the actual bound address of the gateway, which differ if an ephemeral port (`0`)
+/// was requested, or if the configured port was taken.
+async fn get_gateway_address(State(node): State<NodeState>) -> Result<ErasedJson, RestError> {
+    let gateway = node.primary.gateway();
+    let (configured, actual) = (gateway.configured_ip(), gateway.local_ip());
+    Ok(ErasedJson::pretty(json!({ "configured": configured, "actual": actual, "matches": configured == Some(actual) })))
+}
+
+/// Returns the number of outbound messages queued in the gateway, in total and per peer.
+async fn get_outbound_depth(State(node): State<NodeState>) -> Result<ErasedJson, RestError> {
+    let depths = node.primary.gateway().outbound_queue_depths();
+    let total = depths.values().sum::<usize>();
+    let peers = depths
+        .into_iter()
+        .map(|(peer_ip, depth)| (peer_ip.to_string(), json!(depth)))
+        .collect::<serde_json::Map<_, _>>();
+    Ok(ErasedJson::pretty(json!({ "total": total, "peers": peers })))
+}
+
+/// Returns the time (in UNIX milliseconds) the node advanced to each round, for the rounds retained in storage.
+async fn get_round_timeline(State(node): State<NodeState>) -> Result<ErasedJson, RestError> {
+    let timeline = node
+        .primary
+        .storage()
+        .round_timestamps()
+        .into_iter()
+        .map(|(round, timestamp)| (round.to_string(), json!(timestamp)))
+        .collect::<serde_json::Map<_, _>>();
+    Ok(ErasedJson::pretty(timeline))
+}
+
+/// Returns the current round.
+async fn get_current_round(State(node): State<NodeState>) -> Result<ErasedJson, RestError> {
+    Ok(ErasedJson::pretty(node.primary.current_round()))
+}
+
+/// Returns the current round, the highest round observed among the peers' certificates in storage, and the gap.
+async fn get_network_round(State(node): State<NodeState>) -> Result<ErasedJson, RestError> {
+    let (current_round, network_round) = network_round(&node.primary);
+    Ok(ErasedJson::pretty(json!({
+        "current": current_round,
+        "network": network_round,
+        "gap": network_round - current_round,
+    })))
+}
+
+/// Returns the certificates for the given round.
+async fn get_certificates_for_round(
+    State(node): State<NodeState>,
+    Path(round): Path<u64>,
+) -> Result<ErasedJson, RestError> {
+    Ok(ErasedJson::pretty(node.primary.storage().get_certificates_for_round(round)))
+}
+
+/// Returns the timestamps the given certificate carries, as stored: its own timestamp and its batch header's.
+async fn get_certificate_timestamp(
+    State(node): State<NodeState>,
+    Path(id): Path<String>,
+) -> Result<Response, RestError> {
+    let Ok(certificate_id) = Field::<CurrentNetwork>::from_str(&id) else {
+        return Ok((StatusCode::BAD_REQUEST, format!("Invalid certificate ID '{id}'")).into_response());
+    };
+    let Some(certificate) = node.primary.storage().get_certificate(certificate_id) else {
+        return Ok((StatusCode::NOT_FOUND, format!("Certificate '{id}' is not retained in storage")).into_response());
+    };
+    Ok(ErasedJson::pretty(json!({
+        "certificate_id": certificate_id.to_string(),
+        "round": certificate.round(),
+        "author": certificate.author().to_string(),
+        "timestamp": certificate.timestamp(),
+        "batch_timestamp": certificate.batch_header().timestamp(),
+    }))
+    .into_response())
+}
+
+/// Streams the certificates for the given round as newline-delimited JSON, one certificate per line.
+/// Note: Each certificate is serialized as the body is streamed, so the response is never buffered in full.
+async fn get_certificates_for_round_ndjson(State(node): State<NodeState>, Path(round): Path<u64>) -> Response {
+    let certificates = node.primary.storage().get_certificates_for_round(round);
+    let lines = tokio_stream::iter(certificates).map(|certificate| {
+        serde_json::to_vec(&certificate).map(|mut line| {
+            line.push(b'\n');
+            Bytes::from(line)
+        })
+    });
+    ([(header::CONTENT_TYPE, "application/x-ndjson")], Body::from_stream(lines)).into_response()
+}
+
+/// Returns the committed anchor rounds and their leaders, for the rounds retained in storage.
+async fn get_committed_anchors(State(node): State<NodeState>) -> Result<ErasedJson, RestError> {
+    match &node.bft {
+        Some(bft) => {
+            let anchors = bft
+                .committed_anchors()
+                .into_iter()
+                .map(|(round, leader)| json!({ "round": round, "leader": leader.to_string() }))
+                .collect::<Vec<_>>();
+            Ok(ErasedJson::pretty(anchors))
+        }
+        None => Err(RestError::from(anyhow!("BFT is not enabled"))),
+    }
+}
+
+/// Returns whether the BFT has committed the given round, and if so, the leader of the anchor that committed it.
+/// Note: The leader is `null` if the committing anchor has already been garbage collected.
+async fn get_committed_round(State(node): State<NodeState>, Path(round): Path<u64>) -> Result<ErasedJson, RestError> {
+    match &node.bft {
+        Some(bft) => {
+            let is_committed = round <= bft.last_committed_round();
+            // The round is committed by the first anchor at or above it.
+            let anchor = if is_committed {
+                bft.committed_anchors().range(round..).next().map(|(round, leader)| (*round, *leader))
+            } else {
+                None
+            };
+            Ok(ErasedJson::pretty(json!({
+                "round": round,
+                "committed": is_committed,
+                "anchor_round": anchor.map(|(round, _)| round),
+                "leader": anchor.map(|(_, leader)| leader.to_string()),
+            })))
+        }
+        None => Err(RestError::from(anyhow!("BFT is not enabled"))),
+    }
+}
+
+/// Returns the last committed round, the time of the commit, and the time elapsed (in seconds) since.
+async fn get_last_commit(State(node): State<NodeState>) -> Result<ErasedJson, RestError> {
+    match &node.bft {
+        Some(bft) => {
+            let timestamp = bft.last_commit_timestamp();
+            let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+            Ok(ErasedJson::pretty(json!({
+                "round": bft.last_committed_round(),
+                "timestamp": timestamp,
+                "elapsed_secs": timestamp.map(|timestamp| (now.as_secs() as i64).saturating_sub(timestamp)),
+            })))
+        }
+        None => Err(RestError::from(anyhow!("BFT is not enabled"))),
+    }
+}
+
+/// Returns whether the BFT is in normal operation, or recovering from a failed leader (until its next commit),
+/// along with the round and the time elapsed (in seconds) since it entered the state.
+async fn get_bft_state(State(node): State<NodeState>) -> Result<ErasedJson, RestError> {
+    let Some(bft) = &node.bft else {
+        return Err(RestError::from(anyhow!("BFT is not enabled")));
+    };
+    let (leader_state, timestamp) = bft.leader_state();
+    let (state, round) = match leader_state {
+        LeaderState::Normal => ("normal", bft.primary().current_round()),
+        LeaderState::Recovering { failed_round } => ("recovering", failed_round),
+    };
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+    Ok(ErasedJson::pretty(json!({
+        "state": state,
+        "round": round,
+        "since": timestamp,
+        "elapsed_secs": (now.as_secs() as i64).saturating_sub(timestamp),
+    })))
+}
+
+/// Returns the recent samples of the gap between the node's proposing round and the BFT's committed round,
+/// along with the change of the gap over the samples (a persistently growing gap means commits fall behind).
+async fn get_gap_history(State(node): State<NodeState>) -> Result<ErasedJson, RestError> {
+    if node.bft.is_none() {
+        return Err(RestError::from(anyhow!("BFT is not enabled")));
+    }
+    let gap_history = node.gap_history.lock().unwrap().clone();
+    let gap = |(_, proposing_round, committed_round): &GapSample| proposing_round.saturating_sub(*committed_round);
+    let growth = match (gap_history.front(), gap_history.back()) {
+        (Some(first), Some(last)) => gap(last) as i64 - gap(first) as i64,
+        _ => 0,
+    };
+    let samples = gap_history
+        .iter()
+        .map(|sample| {
+            let (timestamp, proposing_round, committed_round) = sample;
+            json!({
+                "timestamp": timestamp,
+                "proposing_round": proposing_round,
+                "committed_round": committed_round,
+                "gap": gap(sample),
+            })
+        })
+        .collect::<Vec<_>>();
+    Ok(ErasedJson::pretty(json!({ "samples": samples, "growth": growth })))
+}
+
+/// Returns the signers (and their stake) collected so far for the node's pending batch proposal, the non-signers,
+/// and the stake still needed to reach the quorum threshold, or `404 Not Found` if the node is not proposing.
+async fn get_pending_signatures(State(node): State<NodeState>) -> Result<Response, RestError> {
+    let Some(proposal) = node.primary.proposed_batch().read().clone() else {
+        return Ok((StatusCode::NOT_FOUND, "The node has no pending batch proposal").into_response());
+    };
+    let committee = node.primary.ledger().get_committee_lookback_for_round(proposal.round())?;
+    // Collect the signers and their stake, sorted by address.
+    let mut signers = proposal
+        .signers()
+        .into_iter()
+        .map(|address| (address.to_string(), committee.get_stake(address)))
+        .collect::<Vec<_>>();
+    signers.sort_unstable();
+    let mut nonsigners = proposal.nonsigners(&committee).iter().map(|address| address.to_string()).collect::<Vec<_>>();
+    nonsigners.sort_unstable();
+    let signed_stake = signers.iter().map(|(_, stake)| *stake).sum::<u64>();
+    let signers =
+        signers.into_iter().map(|(address, stake)| (address, json!(stake))).collect::<serde_json::Map<_, _>>();
+    Ok(ErasedJson::pretty(json!({
+        "round": proposal.round(),
+        "batch_id": proposal.batch_id().to_string(),
+        "signers": signers,
+        "nonsigners": nonsigners,
+        "signed_stake": signed_stake,
+        "quorum_threshold": committee.quorum_threshold(),
+        "stake_needed": committee.quorum_threshold().saturating_sub(signed_stake),
+    }))
+    .into_response())
+}
+
+/// Returns the number of transmissions in the node's pending batch proposal (by type) and their total size in bytes,
+/// along with the maximum number of transmissions per batch, or `404 Not Found` if the node is not proposing.
+async fn get_pending_stats(State(node): State<NodeState>) -> Result<Response, RestError> {
+    let Some(proposal) = node.primary.proposed_batch().read().clone() else {
+        return Ok((StatusCode::NOT_FOUND, "The node has no pending batch proposal").into_response());
+    };
+    let (mut num_solutions, mut num_transactions, mut total_bytes) = (0usize, 0usize, 0usize);
+    for (transmission_id, transmission) in proposal.transmissions() {
+        match transmission_id {
+            TransmissionID::Solution(..) => num_solutions += 1,
+            TransmissionID::Transaction(..) => num_transactions += 1,
+            TransmissionID::Ratification => (),
+        }
+        total_bytes += transmission_num_bytes(transmission)?;
+    }
+    Ok(ErasedJson::pretty(json!({
+        "round": proposal.round(),
+        "batch_id": proposal.batch_id().to_string(),
+        "num_transmissions": proposal.transmissions().len(),
+        "num_solutions": num_solutions,
+        "num_transactions": num_transactions,
+        "total_bytes": total_bytes,
+        "max_transmissions": BatchHeader::<CurrentNetwork>::MAX_TRANSMISSIONS_PER_BATCH,
+    }))
+    .into_response())
+}
+
+/// Returns the rounds and IDs of the certificates in storage from the given validator,
+/// along with the rounds missing a certificate from it, between its first and last certificate.
+async fn get_validator_certificates(
+    State(node): State<NodeState>,
+    Path(address): Path<String>,
+) -> Result<ErasedJson, RestError> {
+    let address = Address::<CurrentNetwork>::from_str(&address)?;
+    let certificate_ids = node.primary.storage().get_certificate_ids_for_author(address);
+    // Determine the rounds in the retained window without a certificate from the validator.
+    let missing_rounds = match (certificate_ids.first(), certificate_ids.last()) {
+        (Some((first, _)), Some((last, _))) => {
+            (*first..=*last).filter(|round| certificate_ids.iter().all(|(r, _)| r != round)).collect::<Vec<_>>()
+        }
+        _ => vec![],
+    };
+    let certificates = certificate_ids
+        .into_iter()
+        .map(|(round, certificate_id)| json!({ "round": round, "certificate_id": certificate_id.to_string() }))
+        .collect::<Vec<_>>();
+    Ok(ErasedJson::pretty(json!({
+        "address": address.to_string(),
+        "certificates": certificates,
+        "missing_rounds": missing_rounds,
+    })))
+}
+
+/// Returns the certificate of the leader for the current even round (or the previous one, on odd rounds), if present.
+async fn get_leader_certificate(State(node): State<NodeState>) -> Result<ErasedJson, RestError> {
+    // Determine the leader round, which is always even.
+    let current_round = node.primary.current_round();
+    let leader_round = current_round.saturating_sub(current_round % 2);
+    // Compute the leader for the round.
+    let committee_lookback = node.primary.ledger().get_committee_lookback_for_round(leader_round)?;
+    let leader = committee_lookback.get_leader(leader_round)?;
+    // Retrieve the leader's certificate for the round.
+    let certificate = node.primary.storage().get_certificate_for_round_with_author(leader_round, leader);
+    Ok(ErasedJson::pretty(json!({
+        "round": leader_round,
+        "leader": leader.to_string(),
+        "certificate": certificate,
+    })))
+}
+
+/// Returns the number of certificates each validator authored in the given round range,
+/// including the members of the current committee that authored none.
+/// Note: The range is bounded to the rounds retained in storage.
+async fn get_participation(
+    State(node): State<NodeState>,
+    Path((from, to)): Path<(u64, u64)>,
+) -> Result<ErasedJson, RestError> {
+    let storage = node.primary.storage();
+    // Bound the range to the retained window.
+    let from = from.max(storage.gc_round() + 1);
+    let to = to.min(storage.current_round());
+
+    let mut counts = count_participation(&node.primary, from, to)?;
+    // Sort the validators by their number of certificates, with the least participating first.
+    counts.sort_by(|_, a, _, b| a.cmp(b));
+    Ok(ErasedJson::pretty(counts))
+}
+
+/// Returns the highest round at which each member of the current committee authored a certificate in storage,
+/// or `null` for the members without a certificate in the retained window (e.g. offline or partitioned).
+async fn get_validators_last_seen(State(node): State<NodeState>) -> Result<ErasedJson, RestError> {
+    let storage = node.primary.storage();
+    let committee = node.primary.ledger().current_committee()?;
+    let mut last_seen = committee.members().keys().map(|address| (*address, None)).collect::<IndexMap<_, _>>();
+    // Walk the retained rounds downwards, so that the first certificate found from a validator is its latest.
+    for round in (storage.gc_round().saturating_add(1)..=storage.current_round()).rev() {
+        for certificate in storage.get_certificates_for_round(round) {
+            last_seen.entry(certificate.author()).or_default().get_or_insert(round);
+        }
+    }
+    let last_seen =
+        last_seen.into_iter().map(|(address, round)| (address.to_string(), json!(round))).collect::<IndexMap<_, _>>();
+    Ok(ErasedJson::pretty(last_seen))
+}
+
+/// Returns the IDs of the certificates reachable from the given round's certificates, grouped by round,
+/// walking the parent links down to the GC floor. The parents missing from storage are listed separately.
+/// Note: The traversal is bounded to `MAX_ANCESTRY_CERTIFICATES` certificates, and reports if it was truncated.
+async fn get_round_ancestry(State(node): State<NodeState>, Path(round): Path<u64>) -> Result<ErasedJson, RestError> {
+    let storage = node.primary.storage();
+    let gc_round = storage.gc_round();
+
+    let mut ancestry = BTreeMap::<u64, Vec<String>>::new();
+    let mut missing = Vec::new();
+    let mut visited = HashSet::new();
+    let mut is_truncated = false;
+    let mut queue = storage.get_certificates_for_round(round).into_iter().collect::<VecDeque<_>>();
+    while let Some(certificate) = queue.pop_front() {
+        if !visited.insert(certificate.id()) {
+            continue;
+        }
+        if visited.len() > MAX_ANCESTRY_CERTIFICATES {
+            is_truncated = true;
+            break;
+        }
+        ancestry.entry(certificate.round()).or_default().push(certificate.id().to_string());
+        // Stop at the GC floor, as the parents below it are no longer retained.
+        if certificate.round().saturating_sub(1) <= gc_round {
+            continue;
+        }
+        for parent_id in certificate.previous_certificate_ids() {
+            match storage.get_certificate(*parent_id) {
+                // Only follow the parents from an earlier round, to guard against malformed links.
+                Some(parent) if parent.round() < certificate.round() => queue.push_back(parent),
+                Some(_) => (),
+                None => missing.push(parent_id.to_string()),
+            }
+        }
+    }
+    missing.sort_unstable();
+    missing.dedup();
+
+    let rounds = ancestry.into_iter().rev().map(|(round, ids)| (round.to_string(), json!(ids)));
+    Ok(ErasedJson::pretty(json!({
+        "round": round,
+        "gc_round": gc_round,
+        "rounds": rounds.collect::<serde_json::Map<_, _>>(),
+        "missing": missing,
+        "is_truncated": is_truncated,
+    })))
+}
+
+/// Returns the certificate DAG for the given round range, as Graphviz DOT.
+/// Note: The range is bounded to the rounds retained in storage.
+async fn get_dag_dot(State(node): State<NodeState>, Path((from, to)): Path<(u64, u64)>) -> Result<Response, RestError> {
+    let storage = node.primary.storage();
+    // Bound the range to the retained window.
+    let from = from.max(storage.gc_round() + 1);
+    let to = to.min(storage.current_round());
+
+    let mut dot = String::from("digraph dag {\n  rankdir=BT;\n  node [shape=box];\n");
+    for round in from..=to {
+        for certificate in storage.get_certificates_for_round(round) {
+            let id = certificate.id();
+            let author = certificate.author().to_string();
+            dot.push_str(&format!("  \"{id}\" [label=\"round {round}\\n{}\"];\n", &author[..12.min(author.len())]));
+            // Only draw the edges to parents within the range.
+            if round == from {
+                continue;
+            }
+            for parent_id in certificate.previous_certificate_ids() {
+                // Highlight the parents that are missing from storage.
+                if !storage.contains_certificate(*parent_id) {
+                    dot.push_str(&format!("  \"{parent_id}\" [label=\"missing\", style=dashed, color=red];\n"));
+                }
+                dot.push_str(&format!("  \"{id}\" -> \"{parent_id}\";\n"));
+            }
+        }
+    }
+    dot.push_str("}\n");
+    Ok(([(axum::http::header::CONTENT_TYPE, "text/vnd.graphviz")], dot).into_response())
+}
+
+/// Returns the bytes of the transmission with the given solution or transaction ID, if it is retained in storage.
+/// Note: Transmissions larger than `MAX_RAW_TRANSMISSION_BYTES` are rejected with `413 Payload Too Large`.
+async fn get_transmission_raw(State(node): State<NodeState>, Path(id): Path<String>) -> Result<Response, RestError> {
+    // Parse the ID as a solution ID, or as a transaction ID.
+    let solution_id = SolutionID::<CurrentNetwork>::from_str(&id).ok();
+    let transaction_id = <CurrentNetwork as Network>::TransactionID::from_str(&id).ok();
+    if solution_id.is_none() && transaction_id.is_none() {
+        return Ok((StatusCode::BAD_REQUEST, format!("Invalid transmission ID '{id}'")).into_response());
+    }
+    let is_match = |transmission_id: &TransmissionID<CurrentNetwork>| match transmission_id {
+        TransmissionID::Solution(solution, _) => Some(*solution) == solution_id,
+        TransmissionID::Transaction(transaction, _) => Some(*transaction) == transaction_id,
+        TransmissionID::Ratification => false,
+    };
+    // Find the transmission among the certificates retained in storage.
+    let storage = node.primary.storage();
+    let transmission = (storage.gc_round().saturating_add(1)..=storage.current_round())
+        .flat_map(|round| storage.get_certificates_for_round(round))
+        .find_map(|certificate| {
+            certificate.transmission_ids().iter().find(|transmission_id| is_match(*transmission_id)).copied()
+        })
+        .and_then(|transmission_id| storage.get_transmission(transmission_id));
+    let Some(transmission) = transmission else {
+        return Ok((StatusCode::NOT_FOUND, format!("Transmission '{id}' is not retained in storage")).into_response());
+    };
+    let bytes = transmission.to_bytes_le()?;
+    if bytes.len() > MAX_RAW_TRANSMISSION_BYTES {
+        let message = format!("Transmission '{id}' is {} bytes (limit: {MAX_RAW_TRANSMISSION_BYTES})", bytes.len());
+        return Ok((StatusCode::PAYLOAD_TOO_LARGE, message).into_response());
+    }
+    Ok(([(header::CONTENT_TYPE, "application/octet-stream")], bytes).into_response())
+}
+
+/// Returns the histogram of the transmission sizes the node has received, keyed by the bucket's upper bound in bytes.
+async fn get_transmission_sizes(State(node): State<NodeState>) -> Result<ErasedJson, RestError> {
+    let histogram = node
+        .primary
+        .transmission_size_histogram()
+        .into_iter()
+        .map(|(upper_bound, count)| (upper_bound.to_string(), json!(count)))
+        .collect::<serde_json::Map<_, _>>();
+    Ok(ErasedJson::pretty(histogram))
+}
+
+/// Returns the ranges of the retained rounds (between the GC floor and the current round) without any certificate,
+/// along with the length of the largest gap. A healthy node has no gaps.
+async fn get_storage_gaps(State(node): State<NodeState>) -> Result<ErasedJson, RestError> {
+    let storage = node.primary.storage();
+    let (gc_round, current_round) = (storage.gc_round(), storage.current_round());
+    // Merge the consecutive rounds without certificates into ranges.
+    let mut gaps = Vec::<(u64, u64)>::new();
+    for round in gc_round.saturating_add(1)..=current_round {
+        if storage.contains_certificates_for_round(round) {
+            continue;
+        }
+        match gaps.last_mut() {
+            Some((_, to)) if *to + 1 == round => *to = round,
+            _ => gaps.push((round, round)),
+        }
+    }
+    let largest_gap = gaps.iter().map(|(from, to)| to - from + 1).max().unwrap_or(0);
+    Ok(ErasedJson::pretty(json!({
+        "gc_round": gc_round,
+        "current_round": current_round,
+        "gaps": gaps.into_iter().map(|(from, to)| json!({ "from": from, "to": to })).collect::<Vec<_>>(),
+        "largest_gap": largest_gap,
+    })))
+}
+
+/// Returns the serialized size of the certificates in the given round, and of the distinct transmissions they include.
+/// Note: Summed over the rounds of a run, this estimates the bandwidth consumed by consensus (excluding the overhead).
+async fn get_round_bytes(State(node): State<NodeState>, Path(round): Path<u64>) -> Result<ErasedJson, RestError> {
+    let storage = node.primary.storage();
+    // Sum the serialized sizes of the certificates in the round.
+    let certificates = storage.get_certificates_for_round(round);
+    let mut certificate_bytes = 0;
+    for certificate in &certificates {
+        certificate_bytes += certificate.to_bytes_le()?.len();
+    }
+    // Sum the serialized sizes of the distinct transmissions referenced by the round.
+    let transmission_ids = certificates.iter().flat_map(|c| c.transmission_ids()).collect::<HashSet<_>>();
+    let (mut transmission_bytes, mut num_missing) = (0, 0);
+    for transmission_id in &transmission_ids {
+        match storage.get_transmission(**transmission_id) {
+            Some(transmission) => transmission_bytes += transmission_num_bytes(&transmission)?,
+            None => num_missing += 1,
+        }
+    }
+    Ok(ErasedJson::pretty(json!({
+        "round": round,
+        "certificates": { "count": certificates.len(), "bytes": certificate_bytes },
+        "transmissions": {
+            "count": transmission_ids.len() - num_missing,
+            "bytes": transmission_bytes,
+            "missing": num_missing,
+        },
+        "total_bytes": certificate_bytes + transmission_bytes,
+    })))
+}
+
+/// Returns the per-round and total counts of certificates and transmissions in storage, and their estimated size.
+async fn get_storage_size(State(node): State<NodeState>) -> Result<ErasedJson, RestError> {
+    // Estimate the size of each round.
+    let sizes = estimate_storage_size(node.primary.storage())?;
+    // Compute the totals.
+    let (num_certificates, num_transmissions, num_bytes) = sizes
+        .values()
+        .fold((0, 0, 0), |(c, t, b), size| (c + size.num_certificates, t + size.num_transmissions, b + size.num_bytes));
+    // Construct the per-round breakdown.
+    let rounds = sizes
+        .iter()
+        .map(|(round, size)| {
+            let breakdown = json!({
+                "certificates": size.num_certificates,
+                "transmissions": size.num_transmissions,
+                "bytes": size.num_bytes,
+            });
+            (round.to_string(), breakdown)
+        })
+        .collect::<serde_json::Map<_, _>>();
+    Ok(ErasedJson::pretty(json!({
+        "rounds": rounds,
+        "total": {
+            "certificates": num_certificates,
+            "transmissions": num_transmissions,
+            "bytes": num_bytes,
+        },
+    })))
+}
+
+/// Returns the configured peers the node is not connected to, with the time (in UNIX seconds) of its latest
+/// connection attempt to each, the number of consecutive failed attempts, and for how long they have been failing.
+async fn get_unreachable_peers(State(node): State<NodeState>) -> Result<ErasedJson, RestError> {
+    let gateway = node.primary.gateway();
+    let connection_attempts = gateway.connection_attempts();
+    let now = now_in_millis() / 1000;
+    let peers = gateway
+        .trusted_validators()
+        .iter()
+        .filter(|peer_ip| !gateway.is_local_ip(**peer_ip) && !gateway.is_connected_ip(**peer_ip))
+        .map(|peer_ip| {
+            let attempts = connection_attempts.get(peer_ip).copied().unwrap_or_default();
+            json!({
+                "peer": peer_ip,
+                "isolated": gateway.is_isolated_ip(*peer_ip),
+                "connecting": gateway.is_connecting_ip(*peer_ip),
+                "last_attempt": (attempts.last_attempt > 0).then_some(attempts.last_attempt),
+                "failures": attempts.num_failures,
+                "failing_secs": attempts.failing_since.map(|since| now.saturating_sub(since)),
+            })
+        })
+        .collect::<Vec<_>>();
+    Ok(ErasedJson::pretty(peers))
+}
+
+/// Disconnects the node from the given peer.
+async fn post_disconnect_peer(
+    State(node): State<NodeState>,
+    Path(peer_ip): Path<SocketAddr>,
+) -> Result<ErasedJson, RestError> {
+    let gateway = node.primary.gateway();
+    // Ensure the node is connected to the peer.
+    if !gateway.is_connected_ip(peer_ip) {
+        return Ok(ErasedJson::pretty(json!({ "peer": peer_ip, "disconnected": false, "reason": "not connected" })));
+    }
+    // Disconnect from the peer.
+    gateway.disconnect(peer_ip).await.map_err(|e| RestError(e.to_string()))?;
+    info!("Disconnected from peer '{peer_ip}' (requested via REST)");
+    Ok(ErasedJson::pretty(json!({ "peer": peer_ip, "disconnected": true })))
+}
+
+/// Isolates the given peer, disconnecting from it and refusing to reconnect until it rejoins.
+async fn post_isolate_peer(
+    State(node): State<NodeState>,
+    Path(peer_ip): Path<SocketAddr>,
+) -> Result<ErasedJson, RestError> {
+    let gateway = node.primary.gateway();
+    // Isolate the peer.
+    let isolated = gateway.isolate_peer(peer_ip);
+    // Disconnect from the peer, if it is connected.
+    if gateway.is_connected_ip(peer_ip) {
+        gateway.disconnect(peer_ip).await.map_err(|e| RestError(e.to_string()))?;
+    }
+    info!("Isolated peer '{peer_ip}' (requested via REST)");
+    Ok(ErasedJson::pretty(json!({ "peer": peer_ip, "isolated": isolated })))
+}
+
+/// Performs a garbage collection pass immediately, based on the given `round` (default: the current round),
+/// and returns the evicted rounds along with the previous and new GC rounds.
+/// Note: This endpoint requires the admin token.
+async fn post_storage_gc(
+    State(node): State<NodeState>,
+    headers: HeaderMap,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Response, RestError> {
+    if let Some(response) = check_admin(&node, &headers) {
+        return Ok(response);
+    }
+    let storage = node.primary.storage();
+    let current_round = storage.current_round();
+    let round = match params.get("round") {
+        Some(round) => u64::from_str(round).map_err(|e| anyhow!("Invalid round '{round}' - {e}"))?,
+        None => current_round,
+    };
+    if round > current_round {
+        return Err(RestError::from(anyhow!("Round {round} is ahead of the current round {current_round}")));
+    }
+    let previous_gc_round = storage.gc_round();
+    let evicted_rounds = storage.garbage_collect_now(round);
+    info!("Garbage collected {} rounds (requested via REST)", evicted_rounds.len());
+    Ok(ErasedJson::pretty(json!({
+        "evicted_rounds": evicted_rounds,
+        "previous_gc_round": previous_gc_round,
+        "gc_round": storage.gc_round(),
+    }))
+    .into_response())
+}
+
+/// Fires two *fake* transactions sharing one transaction ID, but with different payloads, and reports how the
+/// primary resolved the collision: whether it accepted each payload, and whether its workers hold each of them.
+/// Note: The transmission ID of a transaction includes the checksum of its payload, so unless the second payload
+/// is rejected, both payloads are held as distinct transmissions.
+async fn post_fire_collision(State(node): State<NodeState>, headers: HeaderMap) -> Result<Response, RestError> {
+    if let Some(response) = check_admin(&node, &headers) {
+        return Ok(response);
+    }
+    // Sample the shared transaction ID, and the two payloads.
+    let (transaction_id, payloads) = {
+        let mut rng = rand::thread_rng();
+        let transaction_id: <CurrentNetwork as Network>::TransactionID = Field::<CurrentNetwork>::rand(&mut rng).into();
+        let mut sample_payload = || Data::Buffer(Bytes::from((0..1024).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+        (transaction_id, [sample_payload(), sample_payload()])
+    };
+    // Fire the payloads in order, awaiting the primary's callback for each.
+    let mut results = Vec::with_capacity(payloads.len());
+    for (index, transaction) in payloads.into_iter().enumerate() {
+        let checksum = transaction.to_checksum::<CurrentNetwork>()?;
+        let (callback, callback_receiver) = oneshot::channel();
+        node.sender
+            .tx_unconfirmed_transaction
+            .send((transaction_id, transaction, callback))
+            .await
+            .map_err(|e| anyhow!("Failed to send the transaction - {e}"))?;
+        let error = match callback_receiver.await {
+            Ok(Ok(())) => None,
+            Ok(Err(e)) => Some(e.to_string()),
+            Err(_) => Some("The primary dropped the callback".to_string()),
+        };
+        // Determine whether the workers hold the payload.
+        let transmission_id = TransmissionID::Transaction(transaction_id, checksum);
+        let is_held = node.primary.workers().iter().any(|worker| worker.contains_transmission(transmission_id));
+        results.push(json!({
+            "index": index,
+            "checksum": checksum.to_string(),
+            "accepted": error.is_none(),
+            "error": error,
+            "held": is_held,
+        }));
+    }
+    info!("Fired a transaction ID collision for '{transaction_id}' (requested via REST)");
+    Ok(ErasedJson::pretty(json!({ "transaction_id": transaction_id.to_string(), "payloads": results })).into_response())
+}
+
+/// Lifts the isolation of the given peer, allowing the node to reconnect to it.
+async fn post_rejoin_peer(
+    State(node): State<NodeState>,
+    Path(peer_ip): Path<SocketAddr>,
+) -> Result<ErasedJson, RestError> {
+    let rejoined = node.primary.gateway().rejoin_peer(peer_ip);
+    info!("Peer '{peer_ip}' rejoined (requested via REST)");
+    Ok(ErasedJson::pretty(json!({ "peer": peer_ip, "rejoined": rejoined })))
+}
+
+/// Starts up a local server for monitoring the node.
+async fn start_server(
+    bft: Option<BFT<CurrentNetwork>>,
+    primary: Primary<CurrentNetwork>,
+    sender: PrimarySender<CurrentNetwork>,
+    node_id: u16,
+    admin_token: Option<String>,
+    max_connections: Option<usize>,
+    reset_sender: mpsc::Sender<()>,
+) {
+    // If the BFT is enabled, sample the gap between the proposing and committed rounds.
+    let gap_history = Arc::new(Mutex::new(VecDeque::with_capacity(GAP_HISTORY_LEN)));
+    if let Some(bft) = &bft {
+        sample_round_gap(bft.clone(), gap_history.clone());
+    }
+    // Sample the lag of the node behind its peers.
+    let lag_history = Arc::new(Mutex::new(VecDeque::with_capacity(LAG_HISTORY_LEN)));
+    sample_round_lag(primary.clone(), lag_history.clone());
+    // Initialize the routes.
+    let router = Router::new()
+        .route("/", get(|| async { "Hello, World!" }))
+        .route("/leader", get(get_leader))
+        .route("/leader/certificate", get(get_leader_certificate))
+        .route("/leader/:round/inputs", get(get_leader_inputs))
+        .route("/batch/pending/signatures", get(get_pending_signatures))
+        .route("/batch/pending/stats", get(get_pending_stats))
+        .route("/bft/committed", get(get_committed_anchors))
+        .route("/bft/committed/:round", get(get_committed_round))
+        .route("/bft/last-commit", get(get_last_commit))
+        .route("/bft/state", get(get_bft_state))
+        .route("/bft/gap-history", get(get_gap_history))
+        .route("/status", get(get_status))
+        .route("/ready", get(get_ready))
+        .route("/synced", get(get_synced))
+        .route("/committee", get(get_committee))
+        .route("/committee/raw", get(get_committee_raw))
+        .route("/committee/schedule", get(get_committee_schedule))
+        .route("/liveness", get(get_liveness))
+        .route("/gateway/address", get(get_gateway_address))
+        .route("/gateway/outbound-depth", get(get_outbound_depth))
+        .route("/round/current", get(get_current_round))
+        .route("/round/network", get(get_network_round))
+        .route("/round/:round/ancestry", get(get_round_ancestry))
+        .route("/round/:round/bytes", get(get_round_bytes))
+        .route("/round/:round/leader-present", get(get_leader_present))
+        .route("/rounds/timeline", get(get_round_timeline))
+        .route("/certificates/:round", get(get_certificates_for_round))
+        .route("/certificates/:round/ndjson", get(get_certificates_for_round_ndjson))
+        .route("/certificate/:id/timestamp", get(get_certificate_timestamp))
+        .route("/validator/:address/certificates", get(get_validator_certificates))
+        .route("/storage/gaps", get(get_storage_gaps))
+        .route("/storage/size", get(get_storage_size))
+        .route("/storage/gc", post(post_storage_gc))
+        .route("/transmission/:id/raw", get(get_transmission_raw))
+        .route("/transmissions/sizes", get(get_transmission_sizes))
+        .route("/dag/:from/:to", get(get_dag_dot))
+        .route("/participation/:from/:to", get(get_participation))
+        .route("/validators/last-seen", get(get_validators_last_seen))
+        .route("/peers/unreachable", get(get_unreachable_peers))
+        .route("/peers/:addr/disconnect", post(post_disconnect_peer))
+        .route("/peers/:addr/isolate", post(post_isolate_peer))
+        .route("/peers/:addr/rejoin", post(post_rejoin_peer))
+        .route("/reset", post(post_reset))
+        .route("/fire/collision", post(post_fire_collision))
+        // Pass in the `NodeState` to access state.
+        .with_state(NodeState { bft, primary, sender, admin_token, reset_sender, gap_history, lag_history });
+
+    // If set, bound the number of requests served concurrently, and shed the excess with `503 Service Unavailable`.
+    let router = match max_connections {
+        Some(max_connections) => router.layer(
+            ServiceBuilder::new()
+                .layer(HandleErrorLayer::new(|_: BoxError| async { StatusCode::SERVICE_UNAVAILABLE }))
+                .layer(LoadShedLayer::new())
+                .layer(GlobalConcurrencyLimitLayer::new(max_connections)),
+        ),
+        None => router,
+    };
+
+    // Construct the IP address and port.
+    let addr = format!("127.0.0.1:{}", 3000 + node_id);
+
+    // Run the server.
+    info!("Starting the server at '{addr}'...");
+    let rest_addr: SocketAddr = addr.parse().unwrap();
+    let rest_listener = TcpListener::bind(rest_addr).await.unwrap();
+    axum::serve(rest_listener, router.into_make_service_with_connect_info::<SocketAddr>()).await.unwrap();
+}
+
+/// Serves the monitoring server (unless disabled), until the node is reset via `POST /reset`.
+/// Returns `true` if the node was reset, and should be restarted.
+pub async fn serve_until_reset(
+    args: &Args,
+    bft: Option<BFT<CurrentNetwork>>,
+    primary: Primary<CurrentNetwork>,
+    sender: PrimarySender<CurrentNetwork>,
+    dev: Option<u16>,
+) -> Result<bool> {
+    // Initialize the channel to request a reset of the node.
+    let (reset_sender, mut reset_receiver) = mpsc::channel(1);
+
+    let serve = async {
+        if args.no_rest {
+            // Keep the node alive, without the monitoring server.
+            // Note: Do not move this.
+            std::future::pending::<()>().await
+        } else {
+            // Start the monitoring server.
+            let (admin_token, max_connections) = (args.admin_token.clone(), args.rest_max_connections);
+            let (bft, primary) = (bft.clone(), primary.clone());
+            start_server(bft, primary, sender, args.id, admin_token, max_connections, reset_sender).await
+        }
+    };
+    tokio::select! {
+        _ = serve => Ok(false),
+        _ = reset_receiver.recv() => {
+            // Shut down the node.
+            match &bft {
+                Some(bft) => bft.shut_down().await,
+                None => primary.shut_down().await,
+            }
+            // Remove the proposal cache, so that the node restarts from round 0.
+            let proposal_cache = proposal_cache_path(CurrentNetwork::ID, dev);
+            if proposal_cache.exists() {
+                std::fs::remove_file(&proposal_cache)?;
+            }
+            // Remove the checkpoints too, so that they are not restored.
+            if let Some(storage_path) = &args.storage_path {
+                remove_checkpoints(storage_path, args.id)?;
+            }
+            Ok(true)
+        }
+    }
+}
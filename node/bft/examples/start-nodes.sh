@@ -14,7 +14,7 @@ mode=$1
 default_num_nodes=4
 
 # Command to run for each node
-command="cargo +stable r --release --example simple_node"
+command="cargo +stable r --release --features cannons,rest --example simple_node"
 path=$(pwd)
 
 terminal_app=""
@@ -0,0 +1,390 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::LedgerService;
+use async_trait::async_trait;
+use indexmap::IndexMap;
+use parking_lot::Mutex;
+use snarkvm::{
+    ledger::{
+        block::{Block, Transaction},
+        committee::Committee,
+        narwhal::{Data, Subdag, Transmission, TransmissionID},
+        puzzle::{Solution, SolutionID},
+    },
+    prelude::{Address, Field, Network, Result, bail, ensure, narwhal::BatchCertificate},
+};
+use std::{fmt, ops::Range, sync::Arc};
+
+/// The committee members, mapping each address to its `(stake, is_open, commission)`.
+pub type CommitteeMembers<N> = IndexMap<Address<N>, (u64, bool, u8)>;
+
+/// A ledger service wrapper, which rotates the committee every `epoch_length` rounds.
+/// The first epoch uses the committee of the inner ledger, and each subsequent epoch `e`
+/// uses the members at index `(e - 1) % schedule.len()` of the schedule.
+pub struct EpochLedgerService<N: Network> {
+    inner: Arc<dyn LedgerService<N>>,
+    /// The number of rounds in an epoch.
+    epoch_length: u64,
+    /// The schedule of committee members for the epochs after the first.
+    schedule: Vec<CommitteeMembers<N>>,
+    /// The committees of the recently-used epochs.
+    committees: Mutex<IndexMap<u64, Committee<N>>>,
+}
+
+impl<N: Network> fmt::Debug for EpochLedgerService<N> {
+    /// Implements a custom `fmt::Debug` for `EpochLedgerService`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EpochLedgerService")
+            .field("inner", &self.inner)
+            .field("epoch_length", &self.epoch_length)
+            .finish()
+    }
+}
+
+impl<N: Network> EpochLedgerService<N> {
+    /// The maximum number of epoch committees to cache.
+    const MAX_CACHED_COMMITTEES: usize = 8;
+
+    /// Initializes a new epoch ledger service wrapper.
+    /// Note: The schedule is validated upfront, so that a committee exists for every epoch.
+    pub fn new(
+        inner: Arc<dyn LedgerService<N>>,
+        epoch_length: u64,
+        schedule: Vec<CommitteeMembers<N>>,
+    ) -> Result<Self> {
+        // Ensure the epochs start on even rounds, as committees are updated in even rounds.
+        ensure!(
+            epoch_length > 0 && epoch_length % 2 == 0,
+            "The epoch length must be a positive, even number of rounds"
+        );
+        ensure!(!schedule.is_empty(), "The committee schedule must not be empty");
+        // Ensure every committee in the schedule is valid.
+        for (index, members) in schedule.iter().enumerate() {
+            if let Err(error) = Committee::new(epoch_length, members.clone()) {
+                bail!("Invalid committee at index {index} of the schedule - {error}");
+            }
+        }
+        Ok(Self { inner, epoch_length, schedule, committees: Default::default() })
+    }
+
+    /// Returns the number of rounds in an epoch.
+    pub const fn epoch_length(&self) -> u64 {
+        self.epoch_length
+    }
+
+    /// Returns the epoch of the given round.
+    pub const fn epoch(&self, round: u64) -> u64 {
+        round / self.epoch_length
+    }
+
+    /// Returns the committee for the given epoch.
+    fn get_committee_for_epoch(&self, epoch: u64) -> Result<Committee<N>> {
+        // The first epoch uses the committee of the inner ledger.
+        if epoch == 0 {
+            return self.inner.get_committee_for_round(0);
+        }
+        if let Some(committee) = self.committees.lock().get(&epoch) {
+            return Ok(committee.clone());
+        }
+        // Construct the committee, starting at the first round of the epoch.
+        let members = &self.schedule[((epoch - 1) % self.schedule.len() as u64) as usize];
+        let committee = Committee::new(epoch * self.epoch_length, members.clone())?;
+        // Cache the committee, evicting the oldest one if necessary.
+        let mut committees = self.committees.lock();
+        if committees.len() >= Self::MAX_CACHED_COMMITTEES {
+            committees.shift_remove_index(0);
+        }
+        committees.insert(epoch, committee.clone());
+        Ok(committee)
+    }
+}
+
+#[async_trait]
+impl<N: Network> LedgerService<N> for EpochLedgerService<N> {
+    /// Returns the latest round in the ledger.
+    fn latest_round(&self) -> u64 {
+        self.inner.latest_round()
+    }
+
+    /// Returns the latest block height in the ledger.
+    fn latest_block_height(&self) -> u32 {
+        self.inner.latest_block_height()
+    }
+
+    /// Returns the latest block in the ledger.
+    fn latest_block(&self) -> Block<N> {
+        self.inner.latest_block()
+    }
+
+    /// Returns the latest restrictions ID in the ledger.
+    fn latest_restrictions_id(&self) -> Field<N> {
+        self.inner.latest_restrictions_id()
+    }
+
+    /// Returns the latest cached leader and its associated round.
+    fn latest_leader(&self) -> Option<(u64, Address<N>)> {
+        self.inner.latest_leader()
+    }
+
+    /// Updates the latest cached leader and its associated round.
+    fn update_latest_leader(&self, round: u64, leader: Address<N>) {
+        self.inner.update_latest_leader(round, leader);
+    }
+
+    /// Returns `true` if the given block height exists in the ledger.
+    fn contains_block_height(&self, height: u32) -> bool {
+        self.inner.contains_block_height(height)
+    }
+
+    /// Returns the block height for the given block hash, if it exists.
+    fn get_block_height(&self, hash: &N::BlockHash) -> Result<u32> {
+        self.inner.get_block_height(hash)
+    }
+
+    /// Returns the block hash for the given block height, if it exists.
+    fn get_block_hash(&self, height: u32) -> Result<N::BlockHash> {
+        self.inner.get_block_hash(height)
+    }
+
+    /// Returns the block round for the given block height, if it exists.
+    fn get_block_round(&self, height: u32) -> Result<u64> {
+        self.inner.get_block_round(height)
+    }
+
+    /// Returns the block for the given block height.
+    fn get_block(&self, height: u32) -> Result<Block<N>> {
+        self.inner.get_block(height)
+    }
+
+    /// Returns the blocks in the given block range.
+    /// The range is inclusive of the start and exclusive of the end.
+    fn get_blocks(&self, heights: Range<u32>) -> Result<Vec<Block<N>>> {
+        self.inner.get_blocks(heights)
+    }
+
+    /// Returns the solution for the given solution ID.
+    fn get_solution(&self, solution_id: &SolutionID<N>) -> Result<Solution<N>> {
+        self.inner.get_solution(solution_id)
+    }
+
+    /// Returns the unconfirmed transaction for the given transaction ID.
+    fn get_unconfirmed_transaction(&self, transaction_id: N::TransactionID) -> Result<Transaction<N>> {
+        self.inner.get_unconfirmed_transaction(transaction_id)
+    }
+
+    /// Returns the batch certificate for the given batch certificate ID.
+    fn get_batch_certificate(&self, certificate_id: &Field<N>) -> Result<BatchCertificate<N>> {
+        self.inner.get_batch_certificate(certificate_id)
+    }
+
+    /// Returns the committee of the epoch of the latest round.
+    fn current_committee(&self) -> Result<Committee<N>> {
+        self.get_committee_for_epoch(self.epoch(self.latest_round()))
+    }
+
+    /// Returns the committee of the epoch of the given round.
+    fn get_committee_for_round(&self, round: u64) -> Result<Committee<N>> {
+        self.get_committee_for_epoch(self.epoch(round))
+    }
+
+    /// Returns the committee lookback for the given round.
+    fn get_committee_lookback_for_round(&self, round: u64) -> Result<Committee<N>> {
+        // Get the round number for the previous committee. Note, we subtract 2 from odd rounds,
+        // because committees are updated in even rounds.
+        let previous_round = if round % 2 == 0 { round.saturating_sub(1) } else { round.saturating_sub(2) };
+        // Get the committee lookback round.
+        let committee_lookback_round = previous_round.saturating_sub(Committee::<N>::COMMITTEE_LOOKBACK_RANGE);
+        // Retrieve the committee for the committee lookback round.
+        self.get_committee_for_round(committee_lookback_round)
+    }
+
+    /// Returns `true` if the ledger contains the given certificate ID in block history.
+    fn contains_certificate(&self, certificate_id: &Field<N>) -> Result<bool> {
+        self.inner.contains_certificate(certificate_id)
+    }
+
+    /// Returns `true` if the transmission exists in the ledger.
+    fn contains_transmission(&self, transmission_id: &TransmissionID<N>) -> Result<bool> {
+        self.inner.contains_transmission(transmission_id)
+    }
+
+    /// Ensures that the given transmission is not a fee and matches the given transmission ID.
+    fn ensure_transmission_is_well_formed(
+        &self,
+        transmission_id: TransmissionID<N>,
+        transmission: &mut Transmission<N>,
+    ) -> Result<()> {
+        self.inner.ensure_transmission_is_well_formed(transmission_id, transmission)
+    }
+
+    /// Checks the given solution is well-formed.
+    async fn check_solution_basic(&self, solution_id: SolutionID<N>, solution: Data<Solution<N>>) -> Result<()> {
+        self.inner.check_solution_basic(solution_id, solution).await
+    }
+
+    /// Checks the given transaction is well-formed and unique.
+    async fn check_transaction_basic(
+        &self,
+        transaction_id: N::TransactionID,
+        transaction: Data<Transaction<N>>,
+    ) -> Result<()> {
+        self.inner.check_transaction_basic(transaction_id, transaction).await
+    }
+
+    /// Checks the given block is valid next block.
+    fn check_next_block(&self, block: &Block<N>) -> Result<()> {
+        self.inner.check_next_block(block)
+    }
+
+    /// Returns a candidate for the next block in the ledger, using a committed subdag and its transmissions.
+    #[cfg(feature = "ledger-write")]
+    fn prepare_advance_to_next_quorum_block(
+        &self,
+        subdag: Subdag<N>,
+        transmissions: IndexMap<TransmissionID<N>, Transmission<N>>,
+    ) -> Result<Block<N>> {
+        self.inner.prepare_advance_to_next_quorum_block(subdag, transmissions)
+    }
+
+    /// Adds the given block as the next block in the ledger.
+    #[cfg(feature = "ledger-write")]
+    fn advance_to_next_block(&self, block: &Block<N>) -> Result<()> {
+        self.inner.advance_to_next_block(block)
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+    use crate::MockLedgerService;
+    use snarkvm::{ledger::committee::test_helpers::sample_committee, prelude::TestRng};
+
+    type CurrentNetwork = snarkvm::prelude::MainnetV0;
+
+    /// The number of rounds in an epoch, in the tests.
+    const EPOCH_LENGTH: u64 = 10;
+
+    /// Samples an epoch ledger service on top of a mock ledger at the given height, with a schedule of two committees.
+    fn sample_epoch_ledger(
+        height: u32,
+        rng: &mut TestRng,
+    ) -> (EpochLedgerService<CurrentNetwork>, Committee<CurrentNetwork>, Vec<CommitteeMembers<CurrentNetwork>>) {
+        let committee = sample_committee(rng);
+        let inner = Arc::new(MockLedgerService::new_at_height(committee.clone(), height));
+        let schedule = vec![sample_committee(rng).members().clone(), sample_committee(rng).members().clone()];
+        let ledger = EpochLedgerService::new(inner, EPOCH_LENGTH, schedule.clone()).unwrap();
+        (ledger, committee, schedule)
+    }
+
+    #[test]
+    fn test_new_rejects_invalid_epochs() {
+        let rng = &mut TestRng::default();
+        let committee = sample_committee(rng);
+        let inner: Arc<dyn LedgerService<CurrentNetwork>> = Arc::new(MockLedgerService::new(committee.clone()));
+        let schedule = vec![committee.members().clone()];
+
+        // Ensure the epoch length must be positive and even.
+        assert!(EpochLedgerService::new(inner.clone(), 0, schedule.clone()).is_err());
+        assert!(EpochLedgerService::new(inner.clone(), EPOCH_LENGTH + 1, schedule.clone()).is_err());
+        // Ensure the schedule must not be empty.
+        assert!(EpochLedgerService::new(inner.clone(), EPOCH_LENGTH, vec![]).is_err());
+        // Ensure every committee in the schedule must be valid.
+        assert!(EpochLedgerService::new(inner.clone(), EPOCH_LENGTH, vec![Default::default()]).is_err());
+        assert!(EpochLedgerService::new(inner, EPOCH_LENGTH, schedule).is_ok());
+    }
+
+    #[test]
+    fn test_epoch_boundaries() {
+        let rng = &mut TestRng::default();
+        let (ledger, _, _) = sample_epoch_ledger(0, rng);
+
+        assert_eq!(ledger.epoch_length(), EPOCH_LENGTH);
+        assert_eq!(ledger.epoch(0), 0);
+        assert_eq!(ledger.epoch(EPOCH_LENGTH - 1), 0);
+        assert_eq!(ledger.epoch(EPOCH_LENGTH), 1);
+        assert_eq!(ledger.epoch(2 * EPOCH_LENGTH - 1), 1);
+        assert_eq!(ledger.epoch(2 * EPOCH_LENGTH), 2);
+    }
+
+    #[test]
+    fn test_get_committee_for_round() {
+        let rng = &mut TestRng::default();
+        let (ledger, committee, schedule) = sample_epoch_ledger(0, rng);
+
+        // Ensure the first epoch uses the committee of the inner ledger, up to its last round.
+        for round in [0, 1, EPOCH_LENGTH - 1] {
+            assert_eq!(ledger.get_committee_for_round(round).unwrap(), committee);
+        }
+        // Ensure the subsequent epochs cycle through the schedule, starting at the first round of the epoch.
+        for (epoch, index) in [(1, 0), (2, 1), (3, 0), (4, 1)] {
+            for round in [epoch * EPOCH_LENGTH, (epoch + 1) * EPOCH_LENGTH - 1] {
+                let epoch_committee = ledger.get_committee_for_round(round).unwrap();
+                assert_eq!(epoch_committee.members(), &schedule[index]);
+                assert_eq!(epoch_committee.starting_round(), epoch * EPOCH_LENGTH);
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_committee_for_round_after_eviction() {
+        let rng = &mut TestRng::default();
+        let (ledger, _, schedule) = sample_epoch_ledger(0, rng);
+
+        // Look up more epochs than are cached.
+        let num_epochs = EpochLedgerService::<CurrentNetwork>::MAX_CACHED_COMMITTEES as u64 + 2;
+        for epoch in 1..=num_epochs {
+            ledger.get_committee_for_round(epoch * EPOCH_LENGTH).unwrap();
+        }
+        assert_eq!(ledger.committees.lock().len(), EpochLedgerService::<CurrentNetwork>::MAX_CACHED_COMMITTEES);
+        assert!(!ledger.committees.lock().contains_key(&1));
+
+        // Ensure an evicted epoch is reconstructed with the same committee.
+        let epoch_committee = ledger.get_committee_for_round(EPOCH_LENGTH).unwrap();
+        assert_eq!(epoch_committee.members(), &schedule[0]);
+        assert_eq!(epoch_committee.starting_round(), EPOCH_LENGTH);
+    }
+
+    #[test]
+    fn test_current_committee() {
+        let rng = &mut TestRng::default();
+
+        // Note: The latest round of the mock ledger is its latest height.
+        let (ledger, committee, _) = sample_epoch_ledger((EPOCH_LENGTH - 1) as u32, rng);
+        assert_eq!(ledger.current_committee().unwrap(), committee);
+
+        let (ledger, _, schedule) = sample_epoch_ledger(EPOCH_LENGTH as u32, rng);
+        assert_eq!(ledger.current_committee().unwrap().members(), &schedule[0]);
+    }
+
+    #[test]
+    fn test_get_committee_lookback_for_round() {
+        let rng = &mut TestRng::default();
+        let (ledger, committee, schedule) = sample_epoch_ledger(0, rng);
+        let lookback_range = Committee::<CurrentNetwork>::COMMITTEE_LOOKBACK_RANGE;
+
+        // Ensure the lookback of the early rounds saturates to the first epoch.
+        assert_eq!(ledger.get_committee_lookback_for_round(0).unwrap(), committee);
+        assert_eq!(ledger.get_committee_lookback_for_round(1).unwrap(), committee);
+        // Ensure the last rounds whose lookback is in the first epoch use its committee.
+        // Note: An even round looks back from the previous round, and an odd round from two rounds before.
+        let round = EPOCH_LENGTH + lookback_range;
+        assert_eq!(ledger.get_committee_lookback_for_round(round).unwrap(), committee);
+        assert_eq!(ledger.get_committee_lookback_for_round(round + 1).unwrap(), committee);
+        // Ensure the first round whose lookback is in the second epoch uses the committee of the second epoch.
+        let epoch_committee = ledger.get_committee_lookback_for_round(round + 2).unwrap();
+        assert_eq!(epoch_committee.members(), &schedule[0]);
+    }
+}
@@ -18,6 +18,11 @@
 #[macro_use]
 extern crate async_trait;
 
+#[cfg(feature = "epoch")]
+pub mod epoch;
+#[cfg(feature = "epoch")]
+pub use epoch::*;
+
 #[cfg(feature = "ledger")]
 pub mod ledger;
 #[cfg(feature = "ledger")]
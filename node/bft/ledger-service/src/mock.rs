@@ -26,20 +26,40 @@ use snarkvm::{
 
 use indexmap::IndexMap;
 use parking_lot::Mutex;
-use std::{collections::BTreeMap, ops::Range};
+use std::{collections::BTreeMap, fmt, ops::Range};
 use tracing::*;
 
+/// A predicate deciding whether a transmission is valid, given its transmission ID.
+pub type TransmissionValidator<N> = Box<dyn Fn(&TransmissionID<N>) -> bool + Send + Sync>;
+
 /// A mock ledger service that always returns `false`.
-#[derive(Debug)]
 pub struct MockLedgerService<N: Network> {
     committee: Committee<N>,
     height_to_round_and_hash: Mutex<BTreeMap<u32, (u64, N::BlockHash)>>,
+    /// The predicate deciding the validity of the transmissions, if any (otherwise, all of them are valid).
+    validator: Option<TransmissionValidator<N>>,
+}
+
+impl<N: Network> fmt::Debug for MockLedgerService<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockLedgerService")
+            .field("committee", &self.committee)
+            .field("height_to_round_and_hash", &self.height_to_round_and_hash)
+            .field("validator", &self.validator.as_ref().map(|_| "<closure>"))
+            .finish()
+    }
 }
 
 impl<N: Network> MockLedgerService<N> {
     /// Initializes a new mock ledger service.
     pub fn new(committee: Committee<N>) -> Self {
-        Self { committee, height_to_round_and_hash: Default::default() }
+        Self { committee, height_to_round_and_hash: Default::default(), validator: None }
+    }
+
+    /// Initializes a new mock ledger service, which consults the given predicate on the validity of the transmissions.
+    /// The transmissions for which the predicate returns `false` fail the well-formedness and basic checks.
+    pub fn with_validator(committee: Committee<N>, validator: TransmissionValidator<N>) -> Self {
+        Self { committee, height_to_round_and_hash: Default::default(), validator: Some(validator) }
     }
 
     /// Initializes a new mock ledger service at the specified height.
@@ -48,7 +68,17 @@ impl<N: Network> MockLedgerService<N> {
         for i in 0..=height {
             height_to_hash.insert(i, (i as u64 * 2, Field::<N>::from_u32(i).into()));
         }
-        Self { committee, height_to_round_and_hash: Mutex::new(height_to_hash) }
+        Self { committee, height_to_round_and_hash: Mutex::new(height_to_hash), validator: None }
+    }
+
+    /// Ensures the given transmission is valid, according to the validator (if any).
+    fn check_validator(&self, transmission_id: TransmissionID<N>) -> Result<()> {
+        match &self.validator {
+            Some(validator) if !validator(&transmission_id) => {
+                bail!("Transmission '{}' is rejected by the validator", fmt_id(transmission_id))
+            }
+            _ => Ok(()),
+        }
     }
 }
 
@@ -179,11 +209,13 @@ impl<N: Network> LedgerService<N> for MockLedgerService<N> {
     }
 
     /// Ensures that the given transmission is not a fee and matches the given transmission ID.
+    /// Note: If a validator is set, the transmission must also be valid according to it.
     fn ensure_transmission_is_well_formed(
         &self,
         transmission_id: TransmissionID<N>,
         _transmission: &mut Transmission<N>,
     ) -> Result<()> {
+        self.check_validator(transmission_id)?;
         trace!(
             "[MockLedgerService] Ensure transmission ID matches {}.{} - Ok",
             fmt_id(transmission_id),
@@ -193,17 +225,25 @@ impl<N: Network> LedgerService<N> for MockLedgerService<N> {
     }
 
     /// Checks the given solution is well-formed.
-    async fn check_solution_basic(&self, solution_id: SolutionID<N>, _solution: Data<Solution<N>>) -> Result<()> {
+    /// Note: If a validator is set, the solution must also be valid according to it.
+    async fn check_solution_basic(&self, solution_id: SolutionID<N>, solution: Data<Solution<N>>) -> Result<()> {
+        if self.validator.is_some() {
+            self.check_validator(TransmissionID::Solution(solution_id, solution.to_checksum::<N>()?))?;
+        }
         trace!("[MockLedgerService] Check solution basic {:?} - Ok", fmt_id(solution_id));
         Ok(())
     }
 
     /// Checks the given transaction is well-formed and unique.
+    /// Note: If a validator is set, the transaction must also be valid according to it.
     async fn check_transaction_basic(
         &self,
         transaction_id: N::TransactionID,
-        _transaction: Data<Transaction<N>>,
+        transaction: Data<Transaction<N>>,
     ) -> Result<()> {
+        if self.validator.is_some() {
+            self.check_validator(TransmissionID::Transaction(transaction_id, transaction.to_checksum::<N>()?))?;
+        }
         trace!("[MockLedgerService] Check transaction basic {:?} - Ok", fmt_id(transaction_id));
         Ok(())
     }
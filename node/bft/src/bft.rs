@@ -58,6 +58,15 @@ use tokio::{
     task::JoinHandle,
 };
 
+/// The state of the BFT with respect to the leaders of the even rounds.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LeaderState {
+    /// The BFT is advancing with the leaders of the even rounds.
+    Normal,
+    /// The BFT advanced past the given even round without its leader, and has not committed an anchor since.
+    Recovering { failed_round: u64 },
+}
+
 #[derive(Clone)]
 pub struct BFT<N: Network> {
     /// The primary.
@@ -68,6 +77,12 @@ pub struct BFT<N: Network> {
     leader_certificate: Arc<RwLock<Option<BatchCertificate<N>>>>,
     /// The timer for the leader certificate to be received.
     leader_certificate_timer: Arc<AtomicI64>,
+    /// The map of committed anchor rounds to their leaders, for the rounds retained in storage.
+    committed_anchors: Arc<RwLock<BTreeMap<u64, Address<N>>>>,
+    /// The UNIX timestamp (in seconds) of the most recent commit, or `0` if nothing was committed yet.
+    last_commit_timestamp: Arc<AtomicI64>,
+    /// The leader state of the BFT, and the UNIX timestamp (in seconds) it was entered at.
+    leader_state: Arc<RwLock<(LeaderState, i64)>>,
     /// The consensus sender.
     consensus_sender: Arc<OnceCell<ConsensusSender<N>>>,
     /// The spawned handles.
@@ -91,6 +106,9 @@ impl<N: Network> BFT<N> {
             dag: Default::default(),
             leader_certificate: Default::default(),
             leader_certificate_timer: Default::default(),
+            committed_anchors: Default::default(),
+            last_commit_timestamp: Default::default(),
+            leader_state: Arc::new(RwLock::new((LeaderState::Normal, now()))),
             consensus_sender: Default::default(),
             handles: Default::default(),
             lock: Default::default(),
@@ -124,6 +142,11 @@ impl<N: Network> BFT<N> {
         self.primary.is_synced()
     }
 
+    /// Returns the highest round the BFT has committed.
+    pub fn last_committed_round(&self) -> u64 {
+        self.dag.read().last_committed_round()
+    }
+
     /// Returns the primary.
     pub const fn primary(&self) -> &Primary<N> {
         &self.primary
@@ -148,6 +171,24 @@ impl<N: Network> BFT<N> {
     pub const fn leader_certificate(&self) -> &Arc<RwLock<Option<BatchCertificate<N>>>> {
         &self.leader_certificate
     }
+
+    /// Returns the committed anchor rounds and their leaders, for the rounds retained in storage.
+    pub fn committed_anchors(&self) -> BTreeMap<u64, Address<N>> {
+        self.committed_anchors.read().clone()
+    }
+
+    /// Returns the UNIX timestamp (in seconds) of the most recent commit, if the BFT has committed anything.
+    pub fn last_commit_timestamp(&self) -> Option<i64> {
+        match self.last_commit_timestamp.load(Ordering::SeqCst) {
+            0 => None,
+            timestamp => Some(timestamp),
+        }
+    }
+
+    /// Returns the leader state of the BFT, and the UNIX timestamp (in seconds) it was entered at.
+    pub fn leader_state(&self) -> (LeaderState, i64) {
+        *self.leader_state.read()
+    }
 }
 
 impl<N: Network> BFT<N> {
@@ -258,6 +299,14 @@ impl<N: Network> BFT<N> {
             }
             // Update the timer for the leader certificate.
             self.leader_certificate_timer.store(now(), Ordering::SeqCst);
+            // If the even round advanced without its leader, the BFT recovers until its next commit.
+            let has_leader = matches!(self.leader_certificate.read().as_ref(), Some(c) if c.round() == current_round);
+            if current_round % 2 == 0 && !has_leader {
+                let mut leader_state = self.leader_state.write();
+                if leader_state.0 == LeaderState::Normal {
+                    *leader_state = (LeaderState::Recovering { failed_round: current_round }, now());
+                }
+            }
         }
 
         is_ready
@@ -587,8 +636,9 @@ impl<N: Network> BFT<N> {
 
         // Iterate over the leader certificates to commit.
         for leader_certificate in leader_certificates.into_iter().rev() {
-            // Retrieve the leader certificate round.
+            // Retrieve the leader certificate round and author.
             let leader_round = leader_certificate.round();
+            let leader_author = leader_certificate.author();
             // Compute the commit subdag.
             let commit_subdag = match self.order_dag_with_dfs::<ALLOW_LEDGER_ACCESS>(leader_certificate) {
                 Ok(subdag) => subdag,
@@ -704,10 +754,22 @@ impl<N: Network> BFT<N> {
             for certificate in commit_subdag.values().flatten() {
                 dag_write.commit(certificate, self.storage().max_gc_rounds());
             }
+            // Record the committed anchor.
+            self.committed_anchors.write().insert(leader_round, leader_author);
+            // Record the time of the commit.
+            self.last_commit_timestamp.store(now(), Ordering::SeqCst);
+            // Once an anchor is committed, the BFT has recovered from any failed leader.
+            let mut leader_state = self.leader_state.write();
+            if leader_state.0 != LeaderState::Normal {
+                *leader_state = (LeaderState::Normal, now());
+            }
         }
 
         // Perform garbage collection based on the latest committed leader round.
         self.storage().garbage_collect_certificates(latest_leader_round);
+        // Prune the committed anchors that are no longer retained in storage.
+        let gc_round = self.storage().gc_round();
+        self.committed_anchors.write().retain(|round, _| *round > gc_round);
 
         Ok(())
     }
@@ -892,7 +954,7 @@ impl<N: Network> BFT<N> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{BFT, MAX_LEADER_CERTIFICATE_DELAY_IN_SECS, helpers::Storage};
+    use crate::{BFT, LeaderState, MAX_LEADER_CERTIFICATE_DELAY_IN_SECS, helpers::Storage};
     use snarkos_account::Account;
     use snarkos_node_bft_ledger_service::MockLedgerService;
     use snarkos_node_bft_storage_service::BFTMemoryService;
@@ -1188,6 +1250,52 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[tracing_test::traced_test]
+    fn test_leader_state_recovering_without_leader() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        // Sample the certificates.
+        let (_, certificates) = snarkvm::ledger::narwhal::batch_certificate::test_helpers::sample_batch_certificate_with_previous_certificates(
+            3,
+            rng,
+        );
+
+        // Initialize the committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee_for_round_and_members(
+            2,
+            vec![
+                certificates[0].author(),
+                certificates[1].author(),
+                certificates[2].author(),
+                certificates[3].author(),
+            ],
+            rng,
+        );
+
+        // Initialize the ledger.
+        let ledger = Arc::new(MockLedgerService::new(committee.clone()));
+
+        // Initialize the storage, with the quorum of certificates from round 2, excluding the leader's.
+        let leader = committee.get_leader(2).unwrap();
+        let storage = Storage::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 10);
+        for certificate in certificates.iter().filter(|certificate| certificate.author() != leader) {
+            storage.testing_only_insert_certificate_testing_only(certificate.clone());
+        }
+        assert_eq!(storage.current_round(), 2);
+
+        // Initialize the BFT.
+        let account = Account::new(rng)?;
+        let bft = BFT::new(account, storage.clone(), ledger, None, &[], None)?;
+        assert_eq!(bft.leader_state().0, LeaderState::Normal);
+
+        // Advance past round 2 without the leader, as the timer has expired.
+        assert!(bft.update_to_next_round(2));
+        assert_eq!(bft.leader_state().0, LeaderState::Recovering { failed_round: 2 });
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[tracing_test::traced_test]
     async fn test_order_dag_with_dfs() -> Result<()> {
@@ -1440,6 +1548,9 @@ mod tests {
 
         // Commit the leader certificate.
         bft.commit_leader_certificate::<false, false>(leader_certificate.clone()).await.unwrap();
+        // Ensure the committed anchor was recorded.
+        assert_eq!(bft.committed_anchors().get(&leader_certificate.round()), Some(&leader_certificate.author()));
+        assert!(bft.last_commit_timestamp().is_some());
 
         // Simulate a bootup of the BFT.
 
@@ -19,13 +19,14 @@ use crate::{
     MEMORY_POOL_PORT,
     Worker,
     events::{EventCodec, PrimaryPing},
-    helpers::{Cache, PrimarySender, Resolver, Storage, SyncSender, WorkerSender, assign_to_worker},
+    helpers::{Cache, PrimarySender, Resolver, Storage, SyncSender, WorkerSender, assign_to_worker, now},
     spawn_blocking,
 };
 use snarkos_account::Account;
 use snarkos_node_bft_events::{
     BlockRequest,
     BlockResponse,
+    CertificateBatchRequest,
     CertificateRequest,
     CertificateResponse,
     ChallengeRequest,
@@ -64,11 +65,27 @@ use colored::Colorize;
 use futures::SinkExt;
 use indexmap::{IndexMap, IndexSet};
 use parking_lot::{Mutex, RwLock};
-use rand::seq::{IteratorRandom, SliceRandom};
-use std::{collections::HashSet, future::Future, io, net::SocketAddr, sync::Arc, time::Duration};
+use rand::{
+    Rng,
+    SeedableRng,
+    rngs::StdRng,
+    seq::{IteratorRandom, SliceRandom},
+};
+use std::{
+    collections::HashSet,
+    future::Future,
+    io,
+    net::SocketAddr,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::Duration,
+};
 use tokio::{
+    io::{AsyncWriteExt, BufWriter},
     net::TcpStream,
-    sync::{OnceCell, oneshot},
+    sync::{OnceCell, mpsc, oneshot},
     task::{self, JoinHandle},
 };
 use tokio_stream::StreamExt;
@@ -88,6 +105,19 @@ const RESTRICTED_INTERVAL: i64 = (MAX_CONNECTION_ATTEMPTS as u64 * MAX_BATCH_DEL
 const MIN_CONNECTED_VALIDATORS: usize = 175;
 /// The maximum number of validators to send in a validators response event.
 const MAX_VALIDATORS_TO_SEND: usize = 200;
+/// The maximum number of message log entries pending to be written.
+const MAX_MESSAGE_LOG_BACKLOG: usize = 1 << 16;
+
+/// The outbound connection attempts of the gateway to a peer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConnectionAttempts {
+    /// The time (in UNIX seconds) of the latest connection attempt.
+    pub last_attempt: i64,
+    /// The number of consecutive failed connection attempts, reset once a connection succeeds.
+    pub num_failures: u32,
+    /// The time (in UNIX seconds) of the first of the consecutive failed attempts, if any.
+    pub failing_since: Option<i64>,
+}
 
 /// Part of the Gateway API that deals with networking.
 /// This is a separate trait to allow for easier testing/mocking.
@@ -95,6 +125,11 @@ const MAX_VALIDATORS_TO_SEND: usize = 200;
 pub trait Transport<N: Network>: Send + Sync {
     async fn send(&self, peer_ip: SocketAddr, event: Event<N>) -> Option<oneshot::Receiver<io::Result<()>>>;
     fn broadcast(&self, event: Event<N>);
+
+    /// Returns `true` if the node relays the transmissions it received from its peers (the default).
+    fn relays_transmissions(&self) -> bool {
+        true
+    }
 }
 
 #[derive(Clone)]
@@ -128,6 +163,26 @@ pub struct Gateway<N: Network> {
     sync_sender: Arc<OnceCell<SyncSender<N>>>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
+    /// The set of isolated peer IPs, which the gateway refuses to connect to, or accept connections from.
+    isolated_peers: Arc<RwLock<IndexSet<SocketAddr>>>,
+    /// The SOCKS5 proxy used for outbound connections, if any.
+    proxy: Arc<OnceCell<SocketAddr>>,
+    /// The map of peer IPs to the outbound connection attempts to them.
+    connection_attempts: Arc<RwLock<IndexMap<SocketAddr, ConnectionAttempts>>>,
+    /// The numbers of consecutive connection failures to a peer after which they are logged at WARN and ERROR level.
+    connection_failure_thresholds: Arc<RwLock<(u32, u32)>>,
+    /// The maximum time (in milliseconds) allowed for a connection to complete the handshake.
+    handshake_timeout_ms: Arc<AtomicU64>,
+    /// If set, an event is logged each time a peer connects or disconnects.
+    log_connection_events: Arc<AtomicBool>,
+    /// If set, the node does not relay the transmissions it received from its peers to other peers.
+    relay_disabled: Arc<AtomicBool>,
+    /// The fraction of inbound events to drop, and the seeded RNG deciding which, if inbound drops are enabled.
+    inbound_drops: Arc<OnceCell<(f64, Mutex<StdRng>)>>,
+    /// The number of inbound events dropped so far.
+    num_dropped_inbound: Arc<AtomicU64>,
+    /// The sender of the entries to the message log, if the message log is enabled.
+    message_log: Arc<OnceCell<mpsc::Sender<String>>>,
     /// The development mode.
     dev: Option<u16>,
 }
@@ -165,6 +220,16 @@ impl<N: Network> Gateway<N> {
             worker_senders: Default::default(),
             sync_sender: Default::default(),
             handles: Default::default(),
+            isolated_peers: Default::default(),
+            proxy: Default::default(),
+            connection_attempts: Default::default(),
+            connection_failure_thresholds: Arc::new(RwLock::new((1, u32::MAX))),
+            handshake_timeout_ms: Arc::new(AtomicU64::new(<Self as Handshake>::TIMEOUT_MS)),
+            log_connection_events: Default::default(),
+            relay_disabled: Default::default(),
+            inbound_drops: Default::default(),
+            num_dropped_inbound: Default::default(),
+            message_log: Default::default(),
             dev,
         })
     }
@@ -272,6 +337,13 @@ impl<N: Network> Gateway<N> {
         self.tcp.listening_addr().expect("The TCP listener is not enabled")
     }
 
+    /// Returns the IP address the node was configured to listen on, if a listener is configured.
+    /// Note: This differs from the local IP if the configured port is taken or `0` (i.e. ephemeral).
+    pub fn configured_ip(&self) -> Option<SocketAddr> {
+        let config = self.tcp.config();
+        config.listener_ip.map(|ip| SocketAddr::new(ip, config.desired_listening_port.unwrap_or_default()))
+    }
+
     /// Returns `true` if the given IP is this node.
     pub fn is_local_ip(&self, ip: SocketAddr) -> bool {
         ip == self.local_ip()
@@ -389,11 +461,183 @@ impl<N: Network> Gateway<N> {
         self.connected_peers.read().iter().filter_map(|peer_ip| self.resolver.get_address(*peer_ip)).collect()
     }
 
+    /// Returns the number of outbound messages currently queued to be sent, for each connected peer IP.
+    pub fn outbound_queue_depths(&self) -> IndexMap<SocketAddr, usize> {
+        Writing::outbound_queue_depths(self)
+            .into_iter()
+            .filter_map(|(peer_addr, depth)| self.resolver.get_listener(peer_addr).map(|peer_ip| (peer_ip, depth)))
+            .collect()
+    }
+
     /// Returns the list of connected peers.
     pub fn connected_peers(&self) -> &RwLock<IndexSet<SocketAddr>> {
         &self.connected_peers
     }
 
+    /// Returns the trusted validators, which the gateway keeps connecting to.
+    pub fn trusted_validators(&self) -> &IndexSet<SocketAddr> {
+        &self.trusted_validators
+    }
+
+    /// Returns the outbound connection attempts to each peer IP the gateway has dialed.
+    pub fn connection_attempts(&self) -> IndexMap<SocketAddr, ConnectionAttempts> {
+        self.connection_attempts.read().clone()
+    }
+
+    /// Records the outcome of the connection attempt to the given peer IP at the given time (in UNIX seconds),
+    /// and returns the number of consecutive failed connection attempts to the peer.
+    fn record_connection_outcome(&self, peer_ip: SocketAddr, attempt: i64, is_connected: bool) -> u32 {
+        let mut connection_attempts = self.connection_attempts.write();
+        let attempts = connection_attempts.entry(peer_ip).or_default();
+        if is_connected {
+            attempts.num_failures = 0;
+            attempts.failing_since = None;
+        } else {
+            attempts.num_failures += 1;
+            attempts.failing_since.get_or_insert(attempt);
+        }
+        attempts.num_failures
+    }
+
+    /// Returns `true` if the given peer IP is isolated.
+    pub fn is_isolated_ip(&self, ip: SocketAddr) -> bool {
+        self.isolated_peers.read().contains(&ip)
+    }
+
+    /// Isolates the given peer IP, preventing any connections to or from it until it rejoins.
+    /// Returns `true` if the peer IP was not already isolated.
+    /// Note: This does not disconnect the peer, if it is currently connected.
+    pub fn isolate_peer(&self, ip: SocketAddr) -> bool {
+        self.isolated_peers.write().insert(ip)
+    }
+
+    /// Allows the given peer IP to be connected to, and to connect, again.
+    /// Returns `true` if the peer IP was isolated.
+    pub fn rejoin_peer(&self, ip: SocketAddr) -> bool {
+        self.isolated_peers.write().shift_remove(&ip)
+    }
+
+    /// Sets the SOCKS5 proxy used for outbound connections.
+    /// Note: This must be called before the gateway starts connecting to peers.
+    pub fn set_proxy(&self, proxy: SocketAddr) -> Result<()> {
+        self.proxy.set(proxy).map_err(|_| anyhow!("The proxy is already set in the gateway"))
+    }
+
+    /// Sets the maximum time (in milliseconds) allowed for a connection to complete the handshake.
+    pub fn set_handshake_timeout_ms(&self, timeout_ms: u64) {
+        self.handshake_timeout_ms.store(timeout_ms, Ordering::Relaxed);
+    }
+
+    /// Sets whether an event is logged each time a peer connects or disconnects.
+    pub fn set_log_connection_events(&self, enabled: bool) {
+        self.log_connection_events.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Sets whether the node relays the transmissions it received from its peers, when other peers request them.
+    /// With the relay disabled (i.e. for a leaf node), the uncertified transmissions received from peers are not
+    /// served, while the node's own transmissions and the proposed or certified ones still are, so that the batches
+    /// of the node (and of its peers) can still be certified and synced.
+    pub fn set_relay_transmissions(&self, enabled: bool) {
+        self.relay_disabled.store(!enabled, Ordering::Relaxed);
+    }
+
+    /// Sets the numbers of consecutive connection failures to a peer after which the failures are logged at WARN
+    /// and ERROR level, respectively; the failures before are logged at DEBUG level. By default, every failure
+    /// is logged at WARN level, and none at ERROR level.
+    pub fn set_connection_failure_thresholds(&self, warn_after: u32, error_after: u32) -> Result<()> {
+        ensure!(
+            warn_after <= error_after,
+            "The WARN threshold ({warn_after}) must not exceed the ERROR threshold ({error_after})"
+        );
+        *self.connection_failure_thresholds.write() = (warn_after, error_after);
+        Ok(())
+    }
+
+    /// Returns the level to log the given number of consecutive connection failures to a peer at.
+    fn connection_failure_level(&self, num_failures: u32) -> tracing::Level {
+        let (warn_after, error_after) = *self.connection_failure_thresholds.read();
+        if num_failures >= error_after {
+            tracing::Level::ERROR
+        } else if num_failures >= warn_after {
+            tracing::Level::WARN
+        } else {
+            tracing::Level::DEBUG
+        }
+    }
+
+    /// Sets the fraction of inbound events the gateway randomly drops, using an RNG seeded with the given seed.
+    /// Note: This is intended for fault injection (i.e. simulating a lossy network), and should be `0` otherwise.
+    pub fn set_inbound_drop_rate(&self, rate: f64, seed: u64) -> Result<()> {
+        ensure!((0.0..=1.0).contains(&rate), "The inbound drop rate must be between 0 and 1, found {rate}");
+        self.inbound_drops
+            .set((rate, Mutex::new(StdRng::seed_from_u64(seed))))
+            .map_err(|_| anyhow!("The inbound drop rate is already set in the gateway"))
+    }
+
+    /// Returns the number of inbound events dropped so far.
+    pub fn num_dropped_inbound(&self) -> u64 {
+        self.num_dropped_inbound.load(Ordering::Relaxed)
+    }
+
+    /// Returns `true` if the next inbound event should be dropped, per the inbound drop rate.
+    fn should_drop_inbound(&self) -> bool {
+        match self.inbound_drops.get() {
+            Some((rate, rng)) => rng.lock().gen_bool(*rate),
+            None => false,
+        }
+    }
+
+    /// Enables the message log, which appends an entry for every event sent or received by the gateway
+    /// to the file at the given path, as `timestamp_micros,direction,peer_ip,event,size_in_bytes` lines.
+    /// Note: The entries are written by a buffered background writer, and skipped if the writer falls behind.
+    pub fn set_message_log(&self, path: &std::path::Path) -> Result<()> {
+        let file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+        let (sender, mut receiver) = mpsc::channel::<String>(MAX_MESSAGE_LOG_BACKLOG);
+        self.message_log.set(sender).map_err(|_| anyhow!("The message log is already set in the gateway"))?;
+        // Write the entries in the background.
+        let mut writer = BufWriter::new(tokio::fs::File::from_std(file));
+        self.spawn(async move {
+            while let Some(mut entry) = receiver.recv().await {
+                // Write the pending entries, and flush once there are none left.
+                let result = async {
+                    loop {
+                        writer.write_all(entry.as_bytes()).await?;
+                        match receiver.try_recv() {
+                            Ok(next_entry) => entry = next_entry,
+                            Err(_) => break writer.flush().await,
+                        }
+                    }
+                }
+                .await;
+                if let Err(error) = result {
+                    warn!("{CONTEXT} Failed to write to the message log - {error}");
+                }
+            }
+        });
+        Ok(())
+    }
+
+    /// Appends an entry for the given event and its size in bytes to the message log, if the message log is enabled.
+    fn log_message(&self, direction: &str, peer_ip: SocketAddr, event: &Event<N>, size: usize) {
+        if let Some(sender) = self.message_log.get() {
+            let timestamp = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+            let entry = format!("{},{direction},{peer_ip},{},{size}\n", timestamp.as_micros(), event.name());
+            if sender.try_send(entry).is_err() {
+                trace!("{CONTEXT} Skipped the message log entry for '{}' (the writer is behind)", event.name());
+            }
+        }
+    }
+
+    /// Returns the codec for the connection with the given peer, which logs each event it encodes or decodes
+    /// to the message log (if enabled), with the size the codec serialized it to.
+    fn codec_with_message_log(&self, peer_addr: SocketAddr, direction: &'static str) -> EventCodec<N> {
+        let self_ = self.clone();
+        EventCodec::default().with_observer(Arc::new(move |event, size| {
+            let peer_ip = self_.resolver.get_listener(peer_addr).unwrap_or(peer_addr);
+            self_.log_message(direction, peer_ip, event, size);
+        }))
+    }
+
     /// Attempts to connect to the given peer IP.
     pub fn connect(&self, peer_ip: SocketAddr) -> Option<JoinHandle<()>> {
         // Return early if the attempt is against the protocol rules.
@@ -405,10 +649,29 @@ impl<N: Network> Gateway<N> {
         let self_ = self.clone();
         Some(tokio::spawn(async move {
             debug!("Connecting to validator {peer_ip}...");
-            // Attempt to connect to the peer.
-            if let Err(error) = self_.tcp.connect(peer_ip).await {
+            // Record the connection attempt.
+            let attempt = now();
+            self_.connection_attempts.write().entry(peer_ip).or_default().last_attempt = attempt;
+            // Attempt to connect to the peer, through the proxy if one is set.
+            let result = match self_.proxy.get() {
+                Some(proxy) => self_.tcp.connect_via_proxy(peer_ip, *proxy).await,
+                None => self_.tcp.connect(peer_ip).await,
+            };
+            // Record the outcome of the connection attempt.
+            let num_failures = self_.record_connection_outcome(peer_ip, attempt, result.is_ok());
+            if let Err(error) = result {
                 self_.connecting_peers.lock().shift_remove(&peer_ip);
-                warn!("Unable to connect to '{peer_ip}' - {error}");
+                // Escalate the severity of the log as the consecutive failures to the peer accumulate.
+                let level = self_.connection_failure_level(num_failures);
+                let message =
+                    format!("Unable to connect to '{peer_ip}' ({num_failures} consecutive failures) - {error}");
+                if level == tracing::Level::ERROR {
+                    error!("{message}");
+                } else if level == tracing::Level::WARN {
+                    warn!("{message}");
+                } else {
+                    debug!("{message}");
+                }
             }
         }))
     }
@@ -419,6 +682,10 @@ impl<N: Network> Gateway<N> {
         if self.is_local_ip(peer_ip) {
             bail!("{CONTEXT} Dropping connection attempt to '{peer_ip}' (attempted to self-connect)")
         }
+        // Ensure the peer is not isolated.
+        if self.is_isolated_ip(peer_ip) {
+            bail!("{CONTEXT} Dropping connection attempt to '{peer_ip}' (peer is isolated)")
+        }
         // Ensure the node does not surpass the maximum number of peer connections.
         if self.number_of_connected_peers() >= self.max_connected_peers() {
             bail!("{CONTEXT} Dropping connection attempt to '{peer_ip}' (maximum peers reached)")
@@ -440,6 +707,10 @@ impl<N: Network> Gateway<N> {
         if self.is_local_ip(peer_ip) {
             bail!("{CONTEXT} Dropping connection request from '{peer_ip}' (attempted to self-connect)")
         }
+        // Ensure the peer is not isolated.
+        if self.is_isolated_ip(peer_ip) {
+            bail!("{CONTEXT} Dropping connection request from '{peer_ip}' (peer is isolated)")
+        }
         // Ensure the node is not already connecting to this peer.
         if !self.connecting_peers.lock().insert(peer_ip) {
             bail!("{CONTEXT} Dropping connection request from '{peer_ip}' (already shaking hands as the initiator)")
@@ -472,7 +743,14 @@ impl<N: Network> Gateway<N> {
         // Adds a bidirectional map between the listener address and (ambiguous) peer address.
         self.resolver.insert_peer(peer_ip, peer_addr, address);
         // Add a transmission for this peer in the connected peers.
-        self.connected_peers.write().insert(peer_ip);
+        let num_connected = {
+            let mut connected_peers = self.connected_peers.write();
+            connected_peers.insert(peer_ip);
+            connected_peers.len()
+        };
+        if self.log_connection_events.load(Ordering::Relaxed) {
+            info!("Peer '{peer_ip}' connected ({num_connected} connected peers)");
+        }
         #[cfg(feature = "metrics")]
         self.update_metrics();
     }
@@ -501,7 +779,13 @@ impl<N: Network> Gateway<N> {
         // Removes the bidirectional map between the listener address and (ambiguous) peer address.
         self.resolver.remove_peer(peer_ip);
         // Remove this peer from the connected peers, if it exists.
-        self.connected_peers.write().shift_remove(&peer_ip);
+        let (was_connected, num_connected) = {
+            let mut connected_peers = self.connected_peers.write();
+            (connected_peers.shift_remove(&peer_ip), connected_peers.len())
+        };
+        if was_connected && self.log_connection_events.load(Ordering::Relaxed) {
+            info!("Peer '{peer_ip}' disconnected ({num_connected} connected peers)");
+        }
         #[cfg(feature = "metrics")]
         self.update_metrics();
     }
@@ -652,6 +936,21 @@ impl<N: Network> Gateway<N> {
                 }
                 Ok(())
             }
+            Event::CertificateBatchRequest(CertificateBatchRequest { certificate_ids }) => {
+                // If a sync sender was provided, send a certificate request to the sync module for each certificate.
+                if let Some(sync_sender) = self.sync_sender.get() {
+                    for certificate_id in certificate_ids {
+                        // Skip this certificate if the rate limit was exceeded (i.e. it is being spammed).
+                        let num_events = self.cache.insert_inbound_certificate(certificate_id, CACHE_REQUESTS_INTERVAL);
+                        if num_events >= self.max_cache_duplicates() {
+                            continue;
+                        }
+                        // Send the certificate request to the sync module.
+                        let _ = sync_sender.tx_certificate_request.send((peer_ip, certificate_id.into())).await;
+                    }
+                }
+                Ok(())
+            }
             Event::CertificateRequest(certificate_request) => {
                 // If a sync sender was provided, send the certificate request to the sync module.
                 if let Some(sync_sender) = self.sync_sender.get() {
@@ -914,6 +1213,7 @@ impl<N: Network> Gateway<N> {
         for validator_ip in &self.trusted_validators {
             // If the trusted_validator is not connected, attempt to connect to it.
             if !self.is_local_ip(*validator_ip)
+                && !self.is_isolated_ip(*validator_ip)
                 && !self.is_connecting_ip(*validator_ip)
                 && !self.is_connected_ip(*validator_ip)
             {
@@ -991,7 +1291,7 @@ impl<N: Network> Transport<N> for Gateway<N> {
 
         // Increment the cache for certificate, transmission and block events.
         match event {
-            Event::CertificateRequest(_) | Event::CertificateResponse(_) => {
+            Event::CertificateRequest(_) | Event::CertificateResponse(_) | Event::CertificateBatchRequest(_) => {
                 // Update the outbound event cache. This is necessary to ensure we don't under count the outbound events.
                 self.cache.insert_outbound_event(peer_ip, CACHE_EVENTS_INTERVAL);
                 // Send the event to the peer.
@@ -1033,6 +1333,11 @@ impl<N: Network> Transport<N> for Gateway<N> {
             });
         }
     }
+
+    /// Returns `true` if the node relays the transmissions it received from its peers (the default).
+    fn relays_transmissions(&self) -> bool {
+        !self.relay_disabled.load(Ordering::Relaxed)
+    }
 }
 
 impl<N: Network> P2P for Gateway<N> {
@@ -1055,12 +1360,23 @@ impl<N: Network> Reading for Gateway<N> {
 
     /// Creates a [`Decoder`] used to interpret messages from the network.
     /// The `side` param indicates the connection side **from the node's perspective**.
-    fn codec(&self, _peer_addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
-        Default::default()
+    fn codec(&self, peer_addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
+        self.codec_with_message_log(peer_addr, "received")
     }
 
     /// Processes a message received from the network.
     async fn process_message(&self, peer_addr: SocketAddr, message: Self::Message) -> io::Result<()> {
+        // If inbound drops are enabled, randomly discard the message.
+        if self.should_drop_inbound() {
+            let num_dropped = self.num_dropped_inbound.fetch_add(1, Ordering::Relaxed) + 1;
+            debug!(
+                "{CONTEXT} Dropped a '{}' event from '{peer_addr}' ({num_dropped} dropped in total)",
+                message.name()
+            );
+            #[cfg(feature = "metrics")]
+            metrics::increment_counter(metrics::bft::DROPPED_INBOUND_EVENTS);
+            return Ok(());
+        }
         // Process the message. Disconnect if the peer violated the protocol.
         if let Err(error) = self.inbound(peer_addr, message).await {
             if let Some(peer_ip) = self.resolver.get_listener(peer_addr) {
@@ -1090,8 +1406,8 @@ impl<N: Network> Writing for Gateway<N> {
 
     /// Creates an [`Encoder`] used to write the outbound messages to the target stream.
     /// The `side` parameter indicates the connection side **from the node's perspective**.
-    fn codec(&self, _peer_addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
-        Default::default()
+    fn codec(&self, peer_addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
+        self.codec_with_message_log(peer_addr, "sent")
     }
 }
 
@@ -1120,6 +1436,11 @@ impl<N: Network> OnConnect for Gateway<N> {
 
 #[async_trait]
 impl<N: Network> Handshake for Gateway<N> {
+    /// Returns the configured handshake timeout.
+    fn handshake_timeout_ms(&self) -> u64 {
+        self.handshake_timeout_ms.load(Ordering::Relaxed)
+    }
+
     /// Performs the handshake protocol.
     async fn perform_handshake(&self, mut connection: Connection) -> io::Result<Connection> {
         // Perform the handshake.
@@ -1434,6 +1755,7 @@ mod prop_tests {
         sync::Arc,
     };
     use test_strategy::proptest;
+    use tracing::Level;
 
     type CurrentNetwork = MainnetV0;
 
@@ -1605,6 +1927,66 @@ mod prop_tests {
         assert_eq!(gateway.num_workers(), workers.len() as u8);
     }
 
+    #[proptest]
+    fn test_configured_ip(#[strategy(any_valid_prod_gateway())] input: GatewayInput) {
+        let (storage, _, private_key, prod) = input;
+        let account = Account::try_from(private_key).unwrap();
+        let gateway =
+            Gateway::new(account, storage.clone(), storage.ledger().clone(), prod.ip(), &[], prod.port()).unwrap();
+
+        // Ensure the configured IP is the given one, or the unspecified IP on the default port.
+        let default_ip = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), MEMORY_POOL_PORT);
+        assert_eq!(gateway.configured_ip(), Some(prod.ip().unwrap_or(default_ip)));
+    }
+
+    #[proptest]
+    fn test_record_connection_outcome(#[strategy(any_valid_dev_gateway())] input: GatewayInput) {
+        let (storage, _, private_key, dev) = input;
+        let account = Account::try_from(private_key).unwrap();
+        let gateway =
+            Gateway::new(account, storage.clone(), storage.ledger().clone(), dev.ip(), &[], dev.port()).unwrap();
+        let peer_ip = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 1234);
+        let attempts = |gateway: &Gateway<CurrentNetwork>| gateway.connection_attempts()[&peer_ip];
+
+        // Ensure the consecutive failures accumulate, since the first of them.
+        assert_eq!(gateway.record_connection_outcome(peer_ip, 10, false), 1);
+        assert_eq!(gateway.record_connection_outcome(peer_ip, 20, false), 2);
+        assert_eq!((attempts(&gateway).num_failures, attempts(&gateway).failing_since), (2, Some(10)));
+        // Ensure a successful connection resets the failures.
+        assert_eq!(gateway.record_connection_outcome(peer_ip, 30, true), 0);
+        assert_eq!((attempts(&gateway).num_failures, attempts(&gateway).failing_since), (0, None));
+        // Ensure the failures restart from the next failed attempt.
+        assert_eq!(gateway.record_connection_outcome(peer_ip, 40, false), 1);
+        assert_eq!((attempts(&gateway).num_failures, attempts(&gateway).failing_since), (1, Some(40)));
+        assert_eq!(gateway.connection_attempts().len(), 1);
+    }
+
+    #[proptest]
+    fn test_connection_failure_level(#[strategy(any_valid_dev_gateway())] input: GatewayInput) {
+        let (storage, _, private_key, dev) = input;
+        let account = Account::try_from(private_key).unwrap();
+        let gateway =
+            Gateway::new(account, storage.clone(), storage.ledger().clone(), dev.ip(), &[], dev.port()).unwrap();
+
+        // Ensure every failure is logged at WARN level by default.
+        assert_eq!(gateway.connection_failure_level(1), Level::WARN);
+        assert_eq!(gateway.connection_failure_level(u32::MAX - 1), Level::WARN);
+        // Ensure the level escalates exactly at the thresholds.
+        gateway.set_connection_failure_thresholds(3, 5).unwrap();
+        for (num_failures, level) in [(1, Level::DEBUG), (2, Level::DEBUG), (3, Level::WARN), (4, Level::WARN)] {
+            assert_eq!(gateway.connection_failure_level(num_failures), level);
+        }
+        assert_eq!(gateway.connection_failure_level(5), Level::ERROR);
+        assert_eq!(gateway.connection_failure_level(6), Level::ERROR);
+        // Ensure equal thresholds skip the WARN level.
+        gateway.set_connection_failure_thresholds(2, 2).unwrap();
+        assert_eq!(gateway.connection_failure_level(1), Level::DEBUG);
+        assert_eq!(gateway.connection_failure_level(2), Level::ERROR);
+        // Ensure the WARN threshold may not exceed the ERROR threshold.
+        assert!(gateway.set_connection_failure_thresholds(5, 3).is_err());
+        assert_eq!(gateway.connection_failure_level(2), Level::ERROR);
+    }
+
     #[proptest]
     fn test_is_authorized_validator(#[strategy(any_valid_dev_gateway())] input: GatewayInput) {
         let rng = &mut TestRng::default();
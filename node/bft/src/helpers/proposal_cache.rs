@@ -13,8 +13,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::helpers::{Proposal, SignedProposals};
+use crate::helpers::{Proposal, SignedProposals, fmt_id};
 
+use snarkos_node_bft_ledger_service::LedgerService;
 use snarkvm::{
     console::{account::Address, network::Network, program::SUBDAG_CERTIFICATES_DEPTH},
     ledger::narwhal::BatchCertificate,
@@ -23,7 +24,7 @@ use snarkvm::{
 
 use aleo_std::{StorageMode, aleo_ledger_dir};
 use indexmap::IndexSet;
-use std::{fs, path::PathBuf};
+use std::{collections::HashSet, fs, iter, path::PathBuf};
 
 /// Returns the path where a proposal cache file may be stored.
 pub fn proposal_cache_path(network: u16, dev: Option<u16>) -> PathBuf {
@@ -77,6 +78,58 @@ impl<N: Network> ProposalCache<N> {
             && self.signed_proposals.is_valid(expected_signer)
     }
 
+    /// Checks the integrity of the pending certificates, returning a description of each failure (if any).
+    ///
+    /// This method ensures the following invariants, for each pending certificate:
+    /// - The signatures of the author and the signers are valid for the batch ID.
+    /// - The author and the signers are members of the committee for the batch round.
+    /// - The author and the signers have reached the quorum threshold (N - f).
+    /// - The previous certificates are pending certificates, unless the certificate is in the lowest pending round.
+    pub fn check_integrity(&self, ledger: &dyn LedgerService<N>) -> Vec<String> {
+        // Retrieve the pending certificate IDs, and the lowest pending round.
+        let certificate_ids = self.pending_certificates.iter().map(|c| c.id()).collect::<HashSet<_>>();
+        let lowest_round = self.pending_certificates.iter().map(|c| c.round()).min();
+
+        let mut failures = Vec::new();
+        for certificate in &self.pending_certificates {
+            let (round, batch_id, author) = (certificate.round(), certificate.batch_id(), certificate.author());
+            let context = format!("Certificate {} for round {round}", fmt_id(certificate.id()));
+            // Ensure the signatures are valid for the batch ID.
+            if !certificate.batch_header().signature().verify(&author, &[batch_id]) {
+                failures.push(format!("{context} has an invalid signature from its author {author}"));
+            }
+            for signature in certificate.signatures() {
+                if !signature.verify(&signature.to_address(), &[batch_id]) {
+                    failures.push(format!("{context} has an invalid signature from {}", signature.to_address()));
+                }
+            }
+            // Ensure the author and the signers are members of the committee, and reached the quorum threshold.
+            match ledger.get_committee_lookback_for_round(round) {
+                Ok(committee) => {
+                    let signers = iter::once(author)
+                        .chain(certificate.signatures().map(|signature| signature.to_address()))
+                        .collect::<HashSet<_>>();
+                    for signer in signers.iter().filter(|signer| !committee.is_committee_member(**signer)) {
+                        failures.push(format!("{context} is signed by {signer}, who is not in the committee"));
+                    }
+                    if !committee.is_quorum_threshold_reached(&signers) {
+                        failures.push(format!("{context} did not reach the quorum threshold"));
+                    }
+                }
+                Err(e) => failures.push(format!("{context} has no committee - {e}")),
+            }
+            // Ensure the previous certificates resolve within the pending certificates.
+            if Some(round) != lowest_round {
+                let previous_certificate_ids = certificate.previous_certificate_ids();
+                let num_missing = previous_certificate_ids.iter().filter(|id| !certificate_ids.contains(*id)).count();
+                if num_missing > 0 {
+                    failures.push(format!("{context} is missing {num_missing} of its previous certificates"));
+                }
+            }
+        }
+        failures
+    }
+
     /// Returns `true` if a proposal cache exists for the given network and `dev`.
     pub fn exists(dev: Option<u16>) -> bool {
         proposal_cache_path(N::ID, dev).exists()
@@ -187,13 +240,20 @@ impl<N: Network> Default for ProposalCache<N> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::helpers::{proposal::tests::sample_proposal, signed_proposals::tests::sample_signed_proposals};
+    use crate::helpers::{now, proposal::tests::sample_proposal, signed_proposals::tests::sample_signed_proposals};
+    use snarkos_node_bft_ledger_service::MockLedgerService;
     use snarkvm::{
         console::{account::PrivateKey, network::MainnetV0},
-        ledger::narwhal::batch_certificate::test_helpers::sample_batch_certificates,
+        ledger::{
+            committee::{Committee, MIN_VALIDATOR_STAKE, test_helpers::sample_committee},
+            narwhal::{BatchHeader, batch_certificate::test_helpers::sample_batch_certificates},
+        },
+        prelude::{Field, Uniform},
         utilities::TestRng,
     };
 
+    use indexmap::IndexMap;
+
     type CurrentNetwork = MainnetV0;
 
     const ITERATIONS: usize = 100;
@@ -210,6 +270,26 @@ mod tests {
         ProposalCache::new(round, Some(proposal), signed_proposals, pending_certificates)
     }
 
+    /// Samples a certificate for the given round from the given author, signed by the other given signers.
+    fn sample_signed_certificate(
+        author: &PrivateKey<CurrentNetwork>,
+        signers: &[PrivateKey<CurrentNetwork>],
+        round: u64,
+        previous_certificate_ids: IndexSet<Field<CurrentNetwork>>,
+        rng: &mut TestRng,
+    ) -> BatchCertificate<CurrentNetwork> {
+        let committee_id = Field::rand(rng);
+        let batch_header =
+            BatchHeader::new(author, round, now(), committee_id, Default::default(), previous_certificate_ids, rng)
+                .unwrap();
+        let signatures = signers
+            .iter()
+            .filter(|signer| *signer != author)
+            .map(|signer| signer.sign(&[batch_header.batch_id()], rng).unwrap())
+            .collect();
+        BatchCertificate::from(batch_header, signatures).unwrap()
+    }
+
     #[test]
     fn test_bytes() {
         let rng = &mut TestRng::default();
@@ -222,4 +302,69 @@ mod tests {
             assert_eq!(expected, ProposalCache::read_le(&expected_bytes[..]).unwrap());
         }
     }
+
+    #[test]
+    fn test_check_integrity() {
+        let rng = &mut TestRng::default();
+        let ledger = MockLedgerService::new(sample_committee(rng));
+
+        // Ensure a proposal cache without pending certificates has no integrity failures.
+        assert!(ProposalCache::<CurrentNetwork>::default().check_integrity(&ledger).is_empty());
+
+        // Ensure the pending certificates signed outside of the committee are reported.
+        let singer_private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let proposal_cache = sample_proposal_cache(&singer_private_key, rng);
+        assert!(!proposal_cache.check_integrity(&ledger).is_empty());
+    }
+
+    #[test]
+    fn test_check_integrity_failures() {
+        let rng = &mut TestRng::default();
+
+        // Sample a committee of four validators.
+        let private_keys = (0..4).map(|_| PrivateKey::<CurrentNetwork>::new(rng).unwrap()).collect::<Vec<_>>();
+        let members = private_keys
+            .iter()
+            .map(|private_key| (Address::try_from(private_key).unwrap(), (MIN_VALIDATOR_STAKE, true, 0)))
+            .collect::<IndexMap<_, _>>();
+        let ledger = MockLedgerService::new(Committee::new(1, members).unwrap());
+
+        // Sample a chain of certificates: one per validator for round 1, and one for round 2 linking to them.
+        let round_1 = private_keys
+            .iter()
+            .map(|private_key| sample_signed_certificate(private_key, &private_keys, 1, Default::default(), rng))
+            .collect::<IndexSet<_>>();
+        let previous_certificate_ids = round_1.iter().map(|certificate| certificate.id()).collect::<IndexSet<_>>();
+        let certificate = sample_signed_certificate(&private_keys[0], &private_keys, 2, previous_certificate_ids, rng);
+        let check = |certificate: &BatchCertificate<CurrentNetwork>| {
+            let pending_certificates = round_1.iter().chain(iter::once(certificate)).cloned().collect();
+            ProposalCache::new(2, None, Default::default(), pending_certificates).check_integrity(&ledger)
+        };
+
+        // Ensure a sound chain of certificates has no integrity failures.
+        assert!(check(&certificate).is_empty());
+
+        // Ensure a corrupted signature is reported, along with its signer.
+        let signer = Address::try_from(&private_keys[1]).unwrap();
+        let signatures = certificate
+            .signatures()
+            .map(|signature| {
+                if signature.to_address() == signer {
+                    private_keys[1].sign(&[Field::rand(rng)], rng).unwrap()
+                } else {
+                    *signature
+                }
+            })
+            .collect();
+        let corrupted = BatchCertificate::from_unchecked(certificate.batch_header().clone(), signatures).unwrap();
+        let context = format!("Certificate {} for round 2", fmt_id(corrupted.id()));
+        assert_eq!(check(&corrupted), [format!("{context} has an invalid signature from {signer}")]);
+
+        // Ensure a broken link to a previous certificate is reported.
+        let mut previous_certificate_ids = certificate.previous_certificate_ids().clone();
+        previous_certificate_ids.insert(Field::rand(rng));
+        let unlinked = sample_signed_certificate(&private_keys[0], &private_keys, 2, previous_certificate_ids, rng);
+        let context = format!("Certificate {} for round 2", fmt_id(unlinked.id()));
+        assert_eq!(check(&unlinked), [format!("{context} is missing 1 of its previous certificates")]);
+    }
 }
@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::helpers::{check_timestamp_for_liveness, fmt_id};
+use crate::helpers::{check_timestamp_for_liveness, fmt_id, now_in_millis};
 use snarkos_node_bft_ledger_service::LedgerService;
 use snarkos_node_bft_storage_service::StorageService;
 use snarkvm::{
@@ -78,6 +78,12 @@ pub struct StorageInner<N: Network> {
     gc_round: AtomicU64,
     /// The maximum number of rounds to keep in storage.
     max_gc_rounds: u64,
+    /// The map of `round` to the time (in UNIX milliseconds) the storage advanced to it.
+    round_timestamps: RwLock<IndexMap<u64, i64>>,
+    /// The number of rounds a stored `transmission ID` is remembered for deduplication (`0` disables it).
+    dedup_window_rounds: AtomicU64,
+    /// The map of `transmission ID` to the latest round it was stored in, for the deduplication window.
+    seen_transmissions: RwLock<IndexMap<TransmissionID<N>, u64>>,
     /* Once per batch */
     /// The map of `round` to a list of `(certificate ID, batch ID, author)` entries.
     rounds: RwLock<IndexMap<u64, IndexSet<(Field<N>, Field<N>, Address<N>)>>>,
@@ -108,6 +114,9 @@ impl<N: Network> Storage<N> {
             current_round: Default::default(),
             gc_round: Default::default(),
             max_gc_rounds,
+            round_timestamps: Default::default(),
+            dedup_window_rounds: Default::default(),
+            seen_transmissions: Default::default(),
             rounds: Default::default(),
             certificates: Default::default(),
             batch_ids: Default::default(),
@@ -148,6 +157,29 @@ impl<N: Network> Storage<N> {
         self.max_gc_rounds
     }
 
+    /// Returns the time (in UNIX milliseconds) the storage advanced to each round, for the rounds after the GC round.
+    pub fn round_timestamps(&self) -> IndexMap<u64, i64> {
+        self.round_timestamps.read().clone()
+    }
+
+    /// Returns the number of rounds a stored transmission ID is remembered for deduplication.
+    pub fn dedup_window_rounds(&self) -> u64 {
+        self.dedup_window_rounds.load(Ordering::Relaxed)
+    }
+
+    /// Sets the number of rounds a stored transmission ID is remembered for deduplication.
+    ///
+    /// By default (`0`), a transmission is only deduplicated while it is retained in storage.
+    /// A larger window also rejects transmissions that were garbage collected within the last
+    /// `rounds` rounds, at the cost of keeping one entry per transmission ID in memory.
+    pub fn set_dedup_window_rounds(&self, rounds: u64) {
+        self.dedup_window_rounds.store(rounds, Ordering::Relaxed);
+        // Drop the remembered transmission IDs if the window is disabled.
+        if rounds == 0 {
+            self.seen_transmissions.write().clear();
+        }
+    }
+
     /// Increments storage to the next round, updating the current round.
     /// Note: This method is only called once per round, upon certification of the primary's batch.
     pub fn increment_to_next_round(&self, current_round: u64) -> Result<u64> {
@@ -206,6 +238,14 @@ impl<N: Network> Storage<N> {
     fn update_current_round(&self, next_round: u64) {
         // Update the current round.
         self.current_round.store(next_round, Ordering::SeqCst);
+        // Record the time the storage advanced to the round.
+        self.round_timestamps.write().entry(next_round).or_insert_with(now_in_millis);
+        // Forget the transmission IDs that fell out of the deduplication window.
+        let dedup_window_rounds = self.dedup_window_rounds();
+        if dedup_window_rounds > 0 {
+            let oldest_round = next_round.saturating_sub(dedup_window_rounds);
+            self.seen_transmissions.write().retain(|_, round| *round > oldest_round);
+        }
     }
 
     /// Update the storage by performing garbage collection based on the next round.
@@ -224,10 +264,35 @@ impl<N: Network> Storage<N> {
                     self.remove_certificate(certificate.id());
                 }
             }
+            // Remove the timestamps of the GC round(s).
+            self.round_timestamps.write().retain(|round, _| *round > next_gc_round);
             // Update the GC round.
             self.gc_round.store(next_gc_round, Ordering::SeqCst);
         }
     }
+
+    /// Performs a garbage collection pass immediately, based on the given next round,
+    /// and returns the rounds whose certificates were evicted, in ascending order.
+    /// Note: The BFT garbage collects the storage as it commits; this is intended for testing and diagnostics.
+    pub fn garbage_collect_now(&self, next_round: u64) -> Vec<u64> {
+        // Determine the rounds that will be garbage collected.
+        let current_gc_round = self.gc_round();
+        let next_gc_round = next_round.saturating_sub(self.max_gc_rounds);
+        let mut evicted_rounds = if next_gc_round > current_gc_round {
+            self.rounds
+                .read()
+                .keys()
+                .copied()
+                .filter(|round| (current_gc_round..=next_gc_round).contains(round))
+                .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
+        evicted_rounds.sort_unstable();
+        // Perform the garbage collection.
+        self.garbage_collect_certificates(next_round);
+        evicted_rounds
+    }
 }
 
 impl<N: Network> Storage<N> {
@@ -259,6 +324,13 @@ impl<N: Network> Storage<N> {
         self.transmissions.contains_transmission(transmission_id.into())
     }
 
+    /// Returns `true` if the storage contains the specified `transmission ID`,
+    /// or stored it within the deduplication window.
+    pub fn contains_recent_transmission(&self, transmission_id: impl Into<TransmissionID<N>>) -> bool {
+        let transmission_id = transmission_id.into();
+        self.contains_transmission(transmission_id) || self.seen_transmissions.read().contains_key(&transmission_id)
+    }
+
     /// Returns the transmission for the given `transmission ID`.
     /// If the transmission ID does not exist in storage, `None` is returned.
     pub fn get_transmission(&self, transmission_id: impl Into<TransmissionID<N>>) -> Option<Transmission<N>> {
@@ -308,6 +380,32 @@ impl<N: Network> Storage<N> {
         }
     }
 
+    /// Returns the highest round for which storage contains a certificate from an author other than the given `author`.
+    /// If there are no such certificates in storage, `None` is returned.
+    pub fn highest_certificate_round_excluding(&self, author: Address<N>) -> Option<u64> {
+        self.rounds
+            .read()
+            .iter()
+            .filter(|(_, entries)| entries.iter().any(|(_, _, a)| a != &author))
+            .map(|(round, _)| *round)
+            .max()
+    }
+
+    /// Returns the `(round, certificate ID)` pairs of the certificates from the given `author`,
+    /// in ascending round order.
+    pub fn get_certificate_ids_for_author(&self, author: Address<N>) -> Vec<(u64, Field<N>)> {
+        let mut certificate_ids = self
+            .rounds
+            .read()
+            .iter()
+            .flat_map(|(round, entries)| {
+                entries.iter().filter(|(_, _, a)| a == &author).map(|(certificate_id, _, _)| (*round, *certificate_id))
+            })
+            .collect::<Vec<_>>();
+        certificate_ids.sort_unstable_by_key(|(round, _)| *round);
+        certificate_ids
+    }
+
     /// Returns the certificates for the given `round`.
     /// If the round does not exist in storage, `None` is returned.
     pub fn get_certificates_for_round(&self, round: u64) -> IndexSet<BatchCertificate<N>> {
@@ -572,6 +670,14 @@ impl<N: Network> Storage<N> {
         self.rounds.write().entry(round).or_default().insert((certificate_id, batch_id, author));
         // Obtain the certificate's transmission ids.
         let transmission_ids = certificate.transmission_ids().clone();
+        // Remember the transmission IDs for the deduplication window.
+        if self.dedup_window_rounds() > 0 {
+            let mut seen_transmissions = self.seen_transmissions.write();
+            for transmission_id in &transmission_ids {
+                let seen_round = seen_transmissions.entry(*transmission_id).or_default();
+                *seen_round = (*seen_round).max(round);
+            }
+        }
         // Insert the certificate.
         self.certificates.write().insert(certificate_id, certificate);
         // Insert the batch ID.
@@ -967,6 +1073,97 @@ pub(crate) mod tests {
         assert_storage(&storage, &[], &[], &[], &Default::default());
     }
 
+    #[test]
+    fn test_round_timestamps() {
+        let rng = &mut TestRng::default();
+
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Initialize the ledger.
+        let ledger = Arc::new(MockLedgerService::new(committee));
+        // Initialize the storage.
+        let storage = Storage::<CurrentNetwork>::new(ledger, Arc::new(BFTMemoryService::new()), 1);
+        let starting_round = storage.current_round();
+
+        // Advance the storage by two rounds.
+        storage.increment_to_next_round(starting_round).unwrap();
+        storage.increment_to_next_round(starting_round + 1).unwrap();
+        // Ensure each round has a timestamp, in order.
+        let timestamps = storage.round_timestamps();
+        assert_eq!(
+            timestamps.keys().copied().collect::<Vec<_>>(),
+            (starting_round..=starting_round + 2).collect::<Vec<_>>()
+        );
+        assert!(timestamps.values().zip(timestamps.values().skip(1)).all(|(a, b)| a <= b));
+
+        // Ensure the timestamps of the GC rounds are removed.
+        storage.garbage_collect_certificates(starting_round + 2);
+        assert!(storage.round_timestamps().keys().all(|round| *round > storage.gc_round()));
+        assert!(storage.round_timestamps().contains_key(&(starting_round + 2)));
+    }
+
+    #[test]
+    fn test_garbage_collect_now() {
+        let rng = &mut TestRng::default();
+
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Initialize the ledger.
+        let ledger = Arc::new(MockLedgerService::new(committee));
+        // Initialize the storage.
+        let storage = Storage::<CurrentNetwork>::new(ledger, Arc::new(BFTMemoryService::new()), 1);
+
+        // Insert a certificate.
+        let certificate =
+            snarkvm::ledger::narwhal::batch_certificate::test_helpers::sample_batch_certificate_for_round(5, rng);
+        let (round, certificate_id) = (certificate.round(), certificate.id());
+        let (missing_transmissions, _) = sample_transmissions(&certificate, rng);
+        storage.insert_certificate_atomic(certificate, Default::default(), missing_transmissions);
+
+        // Ensure a pass that does not reach the certificate's round evicts nothing.
+        assert!(storage.garbage_collect_now(round).is_empty());
+        assert!(storage.contains_certificate(certificate_id));
+        // Ensure a pass past the certificate's round evicts it, and advances the GC round.
+        assert_eq!(storage.garbage_collect_now(round + 1), vec![round]);
+        assert!(!storage.contains_certificate(certificate_id));
+        assert_eq!(storage.gc_round(), round);
+        // Ensure a repeated pass evicts nothing.
+        assert!(storage.garbage_collect_now(round + 1).is_empty());
+    }
+
+    #[test]
+    fn test_dedup_window_rounds() {
+        let rng = &mut TestRng::default();
+
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Initialize the ledger.
+        let ledger = Arc::new(MockLedgerService::new(committee));
+        // Initialize the storage.
+        let storage = Storage::<CurrentNetwork>::new(ledger, Arc::new(BFTMemoryService::new()), 1);
+        // Remember the stored transmission IDs for 5 rounds.
+        storage.set_dedup_window_rounds(5);
+
+        // Insert a certificate.
+        let certificate = snarkvm::ledger::narwhal::batch_certificate::test_helpers::sample_batch_certificate(rng);
+        let round = certificate.round();
+        let transmission_id = *certificate.transmission_ids().first().unwrap();
+        let (missing_transmissions, _) = sample_transmissions(&certificate, rng);
+        storage.insert_certificate_atomic(certificate, Default::default(), missing_transmissions);
+        assert!(storage.contains_recent_transmission(transmission_id));
+
+        // Ensure the transmission is still deduplicated after it is garbage collected.
+        storage.garbage_collect_certificates(round + 2);
+        assert!(!storage.contains_transmission(transmission_id));
+        assert!(storage.contains_recent_transmission(transmission_id));
+        storage.update_current_round(round + 4);
+        assert!(storage.contains_recent_transmission(transmission_id));
+
+        // Ensure the transmission is forgotten once it falls out of the window.
+        storage.update_current_round(round + 5);
+        assert!(!storage.contains_recent_transmission(transmission_id));
+    }
+
     #[test]
     fn test_certificate_duplicate() {
         let rng = &mut TestRng::default();
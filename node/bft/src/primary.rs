@@ -38,6 +38,7 @@ use crate::{
         init_sync_channels,
         init_worker_channels,
         now,
+        now_in_millis,
     },
     spawn_blocking,
 };
@@ -64,16 +65,20 @@ use indexmap::{IndexMap, IndexSet};
 use parking_lot::{Mutex, RwLock};
 use rayon::prelude::*;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     future::Future,
     net::SocketAddr,
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicI64, AtomicU64, AtomicUsize, Ordering},
+    },
     time::Duration,
 };
 use tokio::{
-    sync::{Mutex as TMutex, OnceCell},
+    sync::{Mutex as TMutex, OnceCell, Semaphore},
     task::JoinHandle,
 };
+use tracing::{Instrument, Span};
 
 /// A helper type for an optional proposed batch.
 pub type ProposedBatch<N> = RwLock<Option<Proposal<N>>>;
@@ -102,6 +107,32 @@ pub struct Primary<N: Network> {
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
     /// The lock for propose_batch.
     propose_lock: Arc<TMutex<u64>>,
+    /// The artificial delay (in milliseconds) injected before proposing and signing batches.
+    straggler_delay_in_ms: Arc<AtomicU64>,
+    /// The offset (in milliseconds) applied to the timestamps of the proposed batches.
+    clock_skew_in_ms: Arc<AtomicI64>,
+    /// The delay (in milliseconds) between running the primary and its first batch proposal.
+    first_proposal_delay_in_ms: Arc<AtomicU64>,
+    /// The instant before which the primary does not propose a batch, set once the primary is run.
+    first_proposal_at: Arc<OnceCell<std::time::Instant>>,
+    /// The minimum interval (in milliseconds) between two batch proposals, or `0` if unthrottled.
+    round_interval_in_ms: Arc<AtomicU64>,
+    /// The highest round the primary proposes a batch for, or `0` if unbounded.
+    max_propose_round: Arc<AtomicU64>,
+    /// The instant of the most recent proposed batch.
+    latest_proposal_instant: Arc<Mutex<Option<std::time::Instant>>>,
+    /// The number of missing certificates requested from a peer per message, or `0` to request them one at a time.
+    certificate_request_batch_size: Arc<AtomicUsize>,
+    /// The histogram of the received unconfirmed transmission sizes, keyed by the bucket's upper bound (in bytes).
+    transmission_sizes: Arc<RwLock<BTreeMap<usize, u64>>>,
+    /// Whether batch proposing and signing is wrapped in a span per round, for distributed tracing.
+    round_spans: Arc<AtomicBool>,
+    /// The permits bounding the number of unconfirmed transmissions processed concurrently, if bounded.
+    transmission_permits: Arc<OnceCell<Arc<Semaphore>>>,
+    /// Whether received unconfirmed transmissions are eagerly deserialized into objects, instead of kept as buffers.
+    eager_materialization: Arc<AtomicBool>,
+    /// The number of materialized transmissions, of failed materializations, and the time spent (in microseconds).
+    materialization_stats: Arc<[AtomicU64; 3]>,
 }
 
 impl<N: Network> Primary<N> {
@@ -135,6 +166,19 @@ impl<N: Network> Primary<N> {
             signed_proposals: Default::default(),
             handles: Default::default(),
             propose_lock: Default::default(),
+            straggler_delay_in_ms: Default::default(),
+            clock_skew_in_ms: Default::default(),
+            first_proposal_delay_in_ms: Default::default(),
+            first_proposal_at: Default::default(),
+            round_interval_in_ms: Default::default(),
+            max_propose_round: Default::default(),
+            latest_proposal_instant: Default::default(),
+            certificate_request_batch_size: Default::default(),
+            transmission_sizes: Default::default(),
+            round_spans: Default::default(),
+            transmission_permits: Default::default(),
+            eager_materialization: Default::default(),
+            materialization_stats: Default::default(),
         })
     }
 
@@ -187,6 +231,9 @@ impl<N: Network> Primary<N> {
     ) -> Result<()> {
         info!("Starting the primary instance of the memory pool...");
 
+        // Determine the instant of the first batch proposal.
+        self.initialize_first_proposal_instant();
+
         // Set the BFT sender.
         if let Some(bft_sender) = &bft_sender {
             // Set the BFT sender in the primary.
@@ -275,6 +322,158 @@ impl<N: Network> Primary<N> {
     pub fn proposed_batch(&self) -> &Arc<ProposedBatch<N>> {
         &self.proposed_batch
     }
+
+    /// Returns the round the primary is currently proposing a batch for, if it is proposing.
+    pub fn proposing_round(&self) -> Option<u64> {
+        self.proposed_batch.read().as_ref().map(|proposal| proposal.round())
+    }
+
+    /// Sets the artificial delay (in milliseconds) injected before proposing and signing batches.
+    /// Note: This is intended for fault injection (i.e. simulating a straggler), and should be `0` otherwise.
+    pub fn set_straggler_delay_in_ms(&self, delay_in_ms: u64) {
+        self.straggler_delay_in_ms.store(delay_in_ms, Ordering::SeqCst);
+    }
+
+    /// Sets the offset (in milliseconds, positive or negative) applied to the timestamps of the proposed batches.
+    /// Note: This is intended for fault injection (i.e. simulating clock skew), and should be `0` otherwise.
+    pub fn set_clock_skew_in_ms(&self, skew_in_ms: i64) {
+        self.clock_skew_in_ms.store(skew_in_ms, Ordering::SeqCst);
+    }
+
+    /// Sets the delay (in milliseconds) between running the primary and its first batch proposal.
+    /// Note: This is intended for testing the startup ordering (e.g. of the BFT and the primary),
+    /// and must be called before the primary is run.
+    pub fn set_first_proposal_delay_in_ms(&self, delay_in_ms: u64) {
+        self.first_proposal_delay_in_ms.store(delay_in_ms, Ordering::SeqCst);
+    }
+
+    /// Sets the instant of the first batch proposal, i.e. the first proposal delay from now.
+    fn initialize_first_proposal_instant(&self) {
+        let first_proposal_delay = Duration::from_millis(self.first_proposal_delay_in_ms.load(Ordering::SeqCst));
+        let _ = self.first_proposal_at.set(std::time::Instant::now() + first_proposal_delay);
+    }
+
+    /// Sets the minimum interval (in milliseconds) between two batch proposals, pacing the rounds.
+    /// By default (`0`), the primary proposes as soon as it is allowed to.
+    /// Note: The interval cannot pace the proposals faster than the protocol's minimum batch delay.
+    pub fn set_round_interval_in_ms(&self, interval_in_ms: u64) {
+        self.round_interval_in_ms.store(interval_in_ms, Ordering::SeqCst);
+    }
+
+    /// Sets the highest round the primary proposes a batch for. Past this round, the primary stops proposing,
+    /// but keeps signing the proposals of its peers and serving their requests.
+    /// By default (`0`), the primary proposes for every round.
+    pub fn set_max_propose_round(&self, max_round: u64) {
+        self.max_propose_round.store(max_round, Ordering::SeqCst);
+    }
+
+    /// Sets the number of missing certificates requested from a peer per message, when fetching the certificates
+    /// referenced by a batch proposal. The batches are requested concurrently, and each is sent as a single
+    /// `CertificateBatchRequest`, so larger batches exchange fewer messages at the cost of larger ones.
+    /// By default, each certificate is requested with its own `CertificateRequest`.
+    pub fn set_certificate_request_batch_size(&self, batch_size: usize) {
+        self.certificate_request_batch_size.store(batch_size.min(N::MAX_CERTIFICATES as usize), Ordering::SeqCst);
+    }
+
+    /// Enables or disables wrapping batch proposing and signing in an INFO-level span per round,
+    /// carrying the round number and the address of the node, for distributed tracing.
+    pub fn set_round_spans(&self, enabled: bool) {
+        self.round_spans.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Returns the span for the given round, if round spans are enabled.
+    fn round_span(&self, round: u64) -> Span {
+        if self.round_spans.load(Ordering::SeqCst) {
+            info_span!("round", round, node = %self.gateway.account().address())
+        } else {
+            Span::none()
+        }
+    }
+
+    /// Sets the maximum number of unconfirmed transmissions the primary processes concurrently.
+    /// By default, the parallelism is unbounded (i.e. each transmission is processed in its own task).
+    /// Note: Once all workers are busy, new transmissions wait in the primary channel, and callers
+    /// block once the channel is at capacity. This must be called before the primary is run.
+    pub fn set_num_transmission_workers(&self, num_workers: usize) -> Result<()> {
+        ensure!(num_workers > 0, "The number of transmission workers must be greater than zero");
+        self.transmission_permits
+            .set(Arc::new(Semaphore::new(num_workers)))
+            .map_err(|_| anyhow!("The number of transmission workers is already set in the primary"))
+    }
+
+    /// Waits for a free transmission worker, if the parallelism is bounded.
+    async fn acquire_transmission_worker(&self) -> Option<tokio::sync::OwnedSemaphorePermit> {
+        match self.transmission_permits.get() {
+            Some(permits) => permits.clone().acquire_owned().await.ok(),
+            None => None,
+        }
+    }
+
+    /// Sets whether received unconfirmed transmissions are eagerly deserialized into objects on ingest.
+    /// By default, transmissions are lazily kept in the form they are received in (e.g. as buffers).
+    pub fn set_eager_materialization(&self, enabled: bool) {
+        self.eager_materialization.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Returns the number of transmissions eagerly materialized, the number that failed to deserialize,
+    /// and the total time spent deserializing them (in microseconds).
+    pub fn materialization_stats(&self) -> (u64, u64, u64) {
+        let [num_materialized, num_failed, time_in_micros] = &*self.materialization_stats;
+        (
+            num_materialized.load(Ordering::Relaxed),
+            num_failed.load(Ordering::Relaxed),
+            time_in_micros.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Deserializes the given transmission into an object, if eager materialization is enabled.
+    /// If the transmission fails to deserialize, it is returned as is.
+    async fn materialize<T: FromBytes + Clone + Send + 'static>(&self, data: Data<T>) -> Data<T> {
+        if !self.eager_materialization.load(Ordering::SeqCst) || !matches!(data, Data::Buffer(_)) {
+            return data;
+        }
+        let [num_materialized, num_failed, time_in_micros] = &*self.materialization_stats;
+        let timer = std::time::Instant::now();
+        let result = data.clone().deserialize().await;
+        time_in_micros.fetch_add(timer.elapsed().as_micros() as u64, Ordering::Relaxed);
+        match result {
+            Ok(object) => {
+                num_materialized.fetch_add(1, Ordering::Relaxed);
+                Data::Object(object)
+            }
+            Err(e) => {
+                num_failed.fetch_add(1, Ordering::Relaxed);
+                trace!("Failed to materialize an unconfirmed transmission - {e}");
+                data
+            }
+        }
+    }
+
+    /// Returns the histogram of the received unconfirmed transmission sizes.
+    /// The keys are the upper bounds (in bytes, as powers of two) of the buckets, and the values are the counts.
+    pub fn transmission_size_histogram(&self) -> BTreeMap<usize, u64> {
+        self.transmission_sizes.read().clone()
+    }
+
+    /// Records the given size (in bytes) of a received transmission in the histogram.
+    fn record_transmission_size(&self, num_bytes: usize) {
+        *self.transmission_sizes.write().entry(num_bytes.next_power_of_two()).or_default() += 1;
+    }
+
+    /// Returns the checksum and the size (in bytes) of the given transmission.
+    /// Note: An object is serialized once, as the checksum is computed over its bytes anyway.
+    fn to_checksum_and_size<T: FromBytes + ToBytes + Send + 'static>(
+        data: &Data<T>,
+    ) -> Result<(N::TransmissionChecksum, usize)> {
+        match data {
+            Data::Buffer(bytes) => Ok((data.to_checksum::<N>()?, bytes.len())),
+            Data::Object(object) => {
+                let bytes = object.to_bytes_le()?;
+                let num_bytes = bytes.len();
+                Ok((Data::<T>::Buffer(bytes.into()).to_checksum::<N>()?, num_bytes))
+            }
+        }
+    }
 }
 
 impl<N: Network> Primary<N> {
@@ -337,6 +536,13 @@ impl<N: Network> Primary<N> {
     /// 3. Set the batch proposal in the primary.
     /// 4. Broadcast the batch header to all validators for signing.
     pub async fn propose_batch(&self) -> Result<()> {
+        // If the first batch proposal is delayed, do not propose a batch until the delay has elapsed.
+        let is_delayed = self.first_proposal_at.get().is_some_and(|instant| std::time::Instant::now() < *instant);
+        if is_delayed {
+            debug!("Skipping batch proposal {}", "(the first proposal is delayed)".dimmed());
+            return Ok(());
+        }
+
         // This function isn't re-entrant.
         let mut lock_guard = self.propose_lock.lock().await;
 
@@ -354,6 +560,13 @@ impl<N: Network> Primary<N> {
         // If the current round is 0, return early.
         ensure!(round > 0, "Round 0 cannot have transaction batches");
 
+        // If the current round is past the maximum round to propose for, return early.
+        let max_round = self.max_propose_round.load(Ordering::SeqCst);
+        if max_round > 0 && round > max_round {
+            debug!("Skipping batch proposal for round {round} {}", format!("(past round {max_round})").dimmed());
+            return Ok(());
+        }
+
         // If the current storage round is below the latest proposal round, then return early.
         if round < *lock_guard {
             warn!("Cannot propose a batch for round {round} - the latest proposal cache round is {}", *lock_guard);
@@ -405,8 +618,16 @@ impl<N: Network> Primary<N> {
         #[cfg(feature = "metrics")]
         metrics::gauge(metrics::bft::PROPOSAL_ROUND, round as f64);
 
+        // If a round interval is set, ensure the primary proposes at most one batch per interval.
+        let round_interval = Duration::from_millis(self.round_interval_in_ms.load(Ordering::SeqCst));
+        if self.latest_proposal_instant.lock().is_some_and(|instant| instant.elapsed() < round_interval) {
+            debug!("Primary is pacing its batch proposals {}", format!("(every {round_interval:?})").dimmed());
+            return Ok(());
+        }
+
         // Ensure that the primary does not create a new proposal too quickly.
-        if let Err(e) = self.check_proposal_timestamp(previous_round, self.gateway.account().address(), now()) {
+        let timestamp = self.skewed_now();
+        if let Err(e) = self.check_proposal_timestamp(previous_round, self.gateway.account().address(), timestamp) {
             debug!("Primary is safely skipping a batch proposal - {}", format!("{e}").dimmed());
             return Ok(());
         }
@@ -576,7 +797,7 @@ impl<N: Network> Primary<N> {
         }
 
         // Determine the current timestamp.
-        let current_timestamp = now();
+        let current_timestamp = self.skewed_now();
 
         *lock_guard = round;
 
@@ -611,10 +832,13 @@ impl<N: Network> Primary<N> {
                 error!("Failed to reinsert transmissions: {e:?}");
             }
         })?;
+        // If a straggler delay is set, sleep before broadcasting the batch.
+        self.straggle().await;
         // Broadcast the batch to all validators for signing.
         self.gateway.broadcast(Event::BatchPropose(batch_header.into()));
         // Set the timestamp of the latest proposed batch.
         *self.latest_proposed_batch_timestamp.write() = proposal.timestamp();
+        *self.latest_proposal_instant.lock() = Some(std::time::Instant::now());
         // Set the proposed batch.
         *self.proposed_batch.write() = Some(proposal);
         Ok(())
@@ -757,6 +981,9 @@ impl<N: Network> Primary<N> {
 
         /* Proceeding to sign the batch. */
 
+        // If a straggler delay is set, sleep before signing the batch.
+        self.straggle().await;
+
         // Retrieve the batch ID.
         let batch_id = batch_header.batch_id();
         // Sign the batch ID.
@@ -1122,7 +1349,8 @@ impl<N: Network> Primary<N> {
                 // If there is no proposed batch, attempt to propose a batch.
                 // Note: Do NOT spawn a task around this function call. Proposing a batch is a critical path,
                 // and only one batch needs be proposed at a time.
-                if let Err(e) = self_.propose_batch().await {
+                let span = self_.round_span(self_.current_round());
+                if let Err(e) = self_.propose_batch().instrument(span).await {
                     warn!("Cannot propose a batch - {e}");
                 }
             }
@@ -1141,7 +1369,9 @@ impl<N: Network> Primary<N> {
                 let self_ = self_.clone();
                 tokio::spawn(async move {
                     // Process the batch proposal.
-                    if let Err(e) = self_.process_batch_propose_from_peer(peer_ip, batch_propose).await {
+                    let span = self_.round_span(batch_propose.round);
+                    let result = self_.process_batch_propose_from_peer(peer_ip, batch_propose).instrument(span).await;
+                    if let Err(e) = result {
                         warn!("Cannot sign a batch from '{peer_ip}' - {e}");
                     }
                 });
@@ -1237,18 +1467,26 @@ impl<N: Network> Primary<N> {
         let self_ = self.clone();
         self.spawn(async move {
             while let Some((solution_id, solution, callback)) = rx_unconfirmed_solution.recv().await {
-                // Compute the checksum for the solution.
-                let Ok(checksum) = solution.to_checksum::<N>() else {
+                // Compute the checksum and the size of the solution.
+                let Ok((checksum, num_bytes)) = Self::to_checksum_and_size(&solution) else {
                     error!("Failed to compute the checksum for the unconfirmed solution");
                     continue;
                 };
+                // Record the size of the solution.
+                self_.record_transmission_size(num_bytes);
                 // Compute the worker ID.
                 let Ok(worker_id) = assign_to_worker((solution_id, checksum), self_.num_workers()) else {
                     error!("Unable to determine the worker ID for the unconfirmed solution");
                     continue;
                 };
+                // Wait for a free transmission worker, if the parallelism is bounded.
+                let permit = self_.acquire_transmission_worker().await;
                 let self_ = self_.clone();
                 tokio::spawn(async move {
+                    // Hold the permit until the solution is processed.
+                    let _permit = permit;
+                    // Materialize the solution, if eager materialization is enabled.
+                    let solution = self_.materialize(solution).await;
                     // Retrieve the worker.
                     let worker = &self_.workers[worker_id as usize];
                     // Process the unconfirmed solution.
@@ -1264,18 +1502,26 @@ impl<N: Network> Primary<N> {
         self.spawn(async move {
             while let Some((transaction_id, transaction, callback)) = rx_unconfirmed_transaction.recv().await {
                 trace!("Primary - Received an unconfirmed transaction '{}'", fmt_id(transaction_id));
-                // Compute the checksum for the transaction.
-                let Ok(checksum) = transaction.to_checksum::<N>() else {
+                // Compute the checksum and the size of the transaction.
+                let Ok((checksum, num_bytes)) = Self::to_checksum_and_size(&transaction) else {
                     error!("Failed to compute the checksum for the unconfirmed transaction");
                     continue;
                 };
+                // Record the size of the transaction.
+                self_.record_transmission_size(num_bytes);
                 // Compute the worker ID.
                 let Ok(worker_id) = assign_to_worker::<N>((&transaction_id, &checksum), self_.num_workers()) else {
                     error!("Unable to determine the worker ID for the unconfirmed transaction");
                     continue;
                 };
+                // Wait for a free transmission worker, if the parallelism is bounded.
+                let permit = self_.acquire_transmission_worker().await;
                 let self_ = self_.clone();
                 tokio::spawn(async move {
+                    // Hold the permit until the transaction is processed.
+                    let _permit = permit;
+                    // Materialize the transaction, if eager materialization is enabled.
+                    let transaction = self_.materialize(transaction).await;
                     // Retrieve the worker.
                     let worker = &self_.workers[worker_id as usize];
                     // Process the unconfirmed transaction.
@@ -1407,6 +1653,23 @@ impl<N: Network> Primary<N> {
         }
     }
 
+    /// Sleeps for the straggler delay, if one is set.
+    async fn straggle(&self) {
+        let delay_in_ms = self.straggler_delay_in_ms.load(Ordering::SeqCst);
+        if delay_in_ms > 0 {
+            trace!("Primary is straggling for {delay_in_ms} ms");
+            tokio::time::sleep(Duration::from_millis(delay_in_ms)).await;
+        }
+    }
+
+    /// Returns the current timestamp (in seconds), offset by the clock skew, if one is set.
+    fn skewed_now(&self) -> i64 {
+        match self.clock_skew_in_ms.load(Ordering::SeqCst) {
+            0 => now(),
+            skew_in_ms => now_in_millis().saturating_add(skew_in_ms).div_euclid(1000),
+        }
+    }
+
     /// Stores the certified batch and broadcasts it to all validators, returning the certificate.
     async fn store_and_broadcast_certificate(&self, proposal: &Proposal<N>, committee: &Committee<N>) -> Result<()> {
         // Create the batch certificate and transmissions.
@@ -1666,8 +1929,8 @@ impl<N: Network> Primary<N> {
         round: u64,
         certificate_ids: &IndexSet<Field<N>>,
     ) -> Result<HashSet<BatchCertificate<N>>> {
-        // Initialize a list for the missing certificates.
-        let mut fetch_certificates = FuturesUnordered::new();
+        // Initialize a list for the missing certificate IDs.
+        let mut missing_certificate_ids = Vec::new();
         // Iterate through the certificate IDs.
         for certificate_id in certificate_ids {
             // Check if the certificate already exists in the ledger.
@@ -1677,27 +1940,31 @@ impl<N: Network> Primary<N> {
             // If we do not have the certificate, request it.
             if !self.storage.contains_certificate(*certificate_id) {
                 trace!("Primary - Found a new certificate ID for round {round} from '{peer_ip}'");
-                // TODO (howardwu): Limit the number of open requests we send to a peer.
-                // Send an certificate request to the peer.
-                fetch_certificates.push(self.sync.send_certificate_request(peer_ip, *certificate_id));
+                missing_certificate_ids.push(*certificate_id);
             }
         }
 
         // If there are no missing certificates, return early.
-        match fetch_certificates.is_empty() {
-            true => return Ok(Default::default()),
-            false => trace!(
-                "Fetching {} missing certificates for round {round} from '{peer_ip}'...",
-                fetch_certificates.len(),
-            ),
+        if missing_certificate_ids.is_empty() {
+            return Ok(Default::default());
         }
+        trace!("Fetching {} missing certificates for round {round} from '{peer_ip}'...", missing_certificate_ids.len());
+
+        // Determine the number of certificates to request per message.
+        let batch_size = self.certificate_request_batch_size.load(Ordering::SeqCst).max(1);
+        // TODO (howardwu): Limit the number of open requests we send to a peer.
+        // Send a certificate request to the peer for each batch of the missing certificates.
+        let mut fetch_certificates = missing_certificate_ids
+            .chunks(batch_size)
+            .map(|batch| self.sync.send_certificate_batch_request(peer_ip, batch))
+            .collect::<FuturesUnordered<_>>();
 
         // Initialize a set for the missing certificates.
-        let mut missing_certificates = HashSet::with_capacity(fetch_certificates.len());
+        let mut missing_certificates = HashSet::with_capacity(missing_certificate_ids.len());
         // Wait for all of the missing certificates to be fetched.
         while let Some(result) = fetch_certificates.next().await {
-            // Insert the missing certificate into the set.
-            missing_certificates.insert(result?);
+            // Insert the missing certificates into the set.
+            missing_certificates.extend(result?);
         }
         // Return the missing certificates.
         Ok(missing_certificates)
@@ -1710,6 +1977,16 @@ impl<N: Network> Primary<N> {
         self.handles.lock().push(tokio::spawn(future));
     }
 
+    /// Returns a snapshot of the proposal cache, as it would be stored on shutdown, without shutting down.
+    /// Note: This is intended for periodic checkpoints, bounding the state lost on an unclean shutdown.
+    pub async fn proposal_cache(&self) -> ProposalCache<N> {
+        let proposal = self.proposed_batch.read().clone();
+        let signed_proposals = self.signed_proposals.read().clone();
+        let latest_round = proposal.as_ref().map(Proposal::round).unwrap_or(*self.propose_lock.lock().await);
+        let pending_certificates = self.storage.get_pending_certificates();
+        ProposalCache::new(latest_round, proposal, signed_proposals, pending_certificates)
+    }
+
     /// Shuts down the primary.
     pub async fn shut_down(&self) {
         info!("Shutting down the primary...");
@@ -1986,6 +2263,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_to_checksum_and_size() {
+        let mut rng = TestRng::default();
+
+        // Check that the checksum matches, and the size is the length of the buffer.
+        let (_, solution) = sample_unconfirmed_solution(&mut rng);
+        let Data::Buffer(bytes) = &solution else { unreachable!() };
+        let (checksum, num_bytes) = Primary::<CurrentNetwork>::to_checksum_and_size(&solution).unwrap();
+        assert_eq!(checksum, solution.to_checksum::<CurrentNetwork>().unwrap());
+        assert_eq!(num_bytes, bytes.len());
+    }
+
     #[tokio::test]
     async fn test_propose_batch() {
         let mut rng = TestRng::default();
@@ -2007,6 +2296,75 @@ mod tests {
         assert!(primary.proposed_batch.read().is_some());
     }
 
+    #[tokio::test]
+    async fn test_propose_batch_with_clock_skew() {
+        let mut rng = TestRng::default();
+        let (primary, _) = primary_without_handlers(&mut rng).await;
+
+        // Skew the clock of the primary by one minute.
+        primary.set_clock_skew_in_ms(60_000);
+
+        // Generate a solution and store it on one of the workers.
+        let (solution_id, solution) = sample_unconfirmed_solution(&mut rng);
+        primary.workers[0].process_unconfirmed_solution(solution_id, solution).await.unwrap();
+
+        // Propose a batch, and ensure its timestamp is skewed.
+        let before = now();
+        assert!(primary.propose_batch().await.is_ok());
+        let timestamp = primary.proposed_batch.read().as_ref().map(|proposal| proposal.timestamp()).unwrap();
+        assert!(timestamp >= before + 59 && timestamp <= now() + 61);
+    }
+
+    #[tokio::test]
+    async fn test_propose_batch_with_first_proposal_delay() {
+        let mut rng = TestRng::default();
+        let (primary, _) = primary_without_handlers(&mut rng).await;
+
+        // Delay the first proposal, as when running the primary.
+        let delay_in_ms = 500;
+        primary.set_first_proposal_delay_in_ms(delay_in_ms);
+        primary.initialize_first_proposal_instant();
+
+        // Generate a solution and store it on one of the workers.
+        let (solution_id, solution) = sample_unconfirmed_solution(&mut rng);
+        primary.workers[0].process_unconfirmed_solution(solution_id, solution).await.unwrap();
+
+        // Ensure the primary does not propose a batch before the delay elapsed.
+        assert!(primary.propose_batch().await.is_ok());
+        assert!(primary.proposed_batch.read().is_none());
+
+        // Ensure the primary proposes a batch once the delay elapsed.
+        tokio::time::sleep(Duration::from_millis(delay_in_ms)).await;
+        assert!(primary.propose_batch().await.is_ok());
+        assert!(primary.proposed_batch.read().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_propose_batch_with_round_interval() {
+        let mut rng = TestRng::default();
+        let (primary, _) = primary_without_handlers(&mut rng).await;
+
+        // Pace the proposals far beyond the minimum batch delay.
+        primary.set_round_interval_in_ms(60_000);
+
+        // Propose a first batch, which is not paced.
+        assert!(primary.propose_batch().await.is_ok());
+        assert!(primary.proposed_batch.read().is_some());
+
+        // Discard the proposal, and sleep past the minimum batch delay.
+        *primary.proposed_batch.write() = None;
+        tokio::time::sleep(Duration::from_secs(MIN_BATCH_DELAY_IN_SECS)).await;
+
+        // Ensure the primary does not propose another batch within the round interval.
+        assert!(primary.propose_batch().await.is_ok());
+        assert!(primary.proposed_batch.read().is_none());
+
+        // Ensure the primary proposes again once the round interval is lifted.
+        primary.set_round_interval_in_ms(0);
+        assert!(primary.propose_batch().await.is_ok());
+        assert!(primary.proposed_batch.read().is_some());
+    }
+
     #[tokio::test]
     async fn test_propose_batch_with_no_transmissions() {
         let mut rng = TestRng::default();
@@ -2020,6 +2378,25 @@ mod tests {
         assert!(primary.proposed_batch.read().is_some());
     }
 
+    #[tokio::test]
+    async fn test_proposal_cache_snapshot() {
+        let mut rng = TestRng::default();
+        let (primary, accounts) = primary_without_handlers(&mut rng).await;
+
+        // Propose a batch with no transmissions.
+        assert!(primary.propose_batch().await.is_ok());
+        let proposal = primary.proposed_batch.read().clone();
+        assert!(proposal.is_some());
+
+        // Ensure the snapshot carries the proposal, without taking it from the primary.
+        let proposal_cache = primary.proposal_cache().await;
+        assert!(proposal_cache.is_valid(accounts[0].1.address()));
+        let (latest_round, cached_proposal, _, _) = proposal_cache.into();
+        assert_eq!(Some(latest_round), proposal.as_ref().map(Proposal::round));
+        assert_eq!(cached_proposal, proposal);
+        assert_eq!(*primary.proposed_batch.read(), proposal);
+    }
+
     #[tokio::test]
     async fn test_propose_batch_in_round() {
         let round = 3;
@@ -2045,6 +2422,45 @@ mod tests {
         assert!(primary.proposed_batch.read().is_some());
     }
 
+    #[tokio::test]
+    async fn test_propose_batch_past_max_round() {
+        let round = 3;
+        let mut rng = TestRng::default();
+        let (primary, accounts) = primary_without_handlers(&mut rng).await;
+
+        // Fill primary storage.
+        store_certificate_chain(&primary, &accounts, round, &mut rng);
+
+        // Sleep for a while to ensure the primary is ready to propose the next round.
+        tokio::time::sleep(Duration::from_secs(MIN_BATCH_DELAY_IN_SECS)).await;
+
+        // Bound the primary's proposals to a round before the current one, and ensure it does not propose.
+        primary.set_max_propose_round(primary.current_round() - 1);
+        assert!(primary.propose_batch().await.is_ok());
+        assert!(primary.proposed_batch.read().is_none());
+
+        // Lift the bound, and ensure the primary proposes again.
+        primary.set_max_propose_round(0);
+        assert!(primary.propose_batch().await.is_ok());
+        assert!(primary.proposed_batch.read().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_missing_certificates_in_storage() {
+        let round = 3;
+        let mut rng = TestRng::default();
+        let (primary, accounts) = primary_without_handlers(&mut rng).await;
+
+        // Fill primary storage.
+        let certificate_ids = store_certificate_chain(&primary, &accounts, round, &mut rng);
+        assert!(!certificate_ids.is_empty());
+
+        // Ensure the certificates already in storage are not fetched from the peer.
+        let peer_ip = SocketAddr::from(([127, 0, 0, 1], 1234));
+        let missing_certificates = primary.fetch_missing_certificates(peer_ip, round - 1, &certificate_ids).await;
+        assert!(missing_certificates.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_propose_batch_skip_transmissions_from_previous_certificates() {
         let round = 3;
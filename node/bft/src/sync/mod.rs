@@ -21,7 +21,7 @@ use crate::{
     helpers::{BFTSender, Pending, Storage, SyncReceiver, fmt_id, max_redundant_requests},
     spawn_blocking,
 };
-use snarkos_node_bft_events::{CertificateRequest, CertificateResponse, Event};
+use snarkos_node_bft_events::{CertificateBatchRequest, CertificateRequest, CertificateResponse, Event};
 use snarkos_node_bft_ledger_service::LedgerService;
 use snarkos_node_sync::{BlockSync, BlockSyncMode, locators::BlockLocators};
 use snarkvm::{
@@ -31,6 +31,8 @@ use snarkvm::{
 };
 
 use anyhow::{Result, bail};
+use futures::future::try_join_all;
+use indexmap::IndexSet;
 use parking_lot::Mutex;
 use rayon::prelude::*;
 use std::{collections::HashMap, future::Future, net::SocketAddr, sync::Arc, time::Duration};
@@ -575,6 +577,61 @@ impl<N: Network> Sync<N> {
         peer_ip: SocketAddr,
         certificate_id: Field<N>,
     ) -> Result<BatchCertificate<N>> {
+        // Insert the certificate ID into the pending queue.
+        let (should_send_request, callback_receiver) = self.insert_pending_certificate_request(peer_ip, certificate_id);
+
+        // If the number of requests is less than or equal to the redundancy factor, send the certificate request to the peer.
+        if should_send_request {
+            // Send the certificate request to the peer.
+            if self.gateway.send(peer_ip, Event::CertificateRequest(certificate_id.into())).await.is_none() {
+                bail!("Unable to fetch batch certificate {certificate_id} - failed to send request")
+            }
+        }
+        // Wait for the certificate to be fetched.
+        Self::wait_for_certificate(certificate_id, callback_receiver).await
+    }
+
+    /// Sends a single request for the given certificates to the specified peer, and returns the fetched certificates.
+    /// Note: The peer responds with a `CertificateResponse` for each certificate, as for single requests.
+    pub async fn send_certificate_batch_request(
+        &self,
+        peer_ip: SocketAddr,
+        certificate_ids: &[Field<N>],
+    ) -> Result<Vec<BatchCertificate<N>>> {
+        // Insert the certificate IDs into the pending queue.
+        let mut request_ids = IndexSet::with_capacity(certificate_ids.len());
+        let mut callback_receivers = Vec::with_capacity(certificate_ids.len());
+        for certificate_id in certificate_ids {
+            let (should_send_request, callback_receiver) =
+                self.insert_pending_certificate_request(peer_ip, *certificate_id);
+            if should_send_request {
+                request_ids.insert(*certificate_id);
+            }
+            callback_receivers.push(Self::wait_for_certificate(*certificate_id, callback_receiver));
+        }
+
+        // Send the certificate IDs to the peer in one message, if there is more than one.
+        let event = match request_ids.len() {
+            0 => None,
+            1 => Some(Event::CertificateRequest(request_ids[0].into())),
+            _ => Some(Event::CertificateBatchRequest(CertificateBatchRequest::new(request_ids))),
+        };
+        if let Some(event) = event {
+            if self.gateway.send(peer_ip, event).await.is_none() {
+                bail!("Unable to fetch {} batch certificates - failed to send request", certificate_ids.len())
+            }
+        }
+        // Wait for the certificates to be fetched.
+        try_join_all(callback_receivers).await
+    }
+
+    /// Inserts a request for the given certificate from the specified peer into the pending queue.
+    /// Returns `true` if the request should be sent to the peer, and the receiver of the fetched certificate.
+    fn insert_pending_certificate_request(
+        &self,
+        peer_ip: SocketAddr,
+        certificate_id: Field<N>,
+    ) -> (bool, oneshot::Receiver<BatchCertificate<N>>) {
         // Initialize a oneshot channel.
         let (callback_sender, callback_receiver) = oneshot::channel();
         // Determine how many sent requests are pending.
@@ -590,19 +647,20 @@ impl<N: Network> Sync<N> {
         // Insert the certificate ID into the pending queue.
         self.pending.insert(certificate_id, peer_ip, Some((callback_sender, should_send_request)));
 
-        // If the number of requests is less than or equal to the redundancy factor, send the certificate request to the peer.
-        if should_send_request {
-            // Send the certificate request to the peer.
-            if self.gateway.send(peer_ip, Event::CertificateRequest(certificate_id.into())).await.is_none() {
-                bail!("Unable to fetch batch certificate {certificate_id} - failed to send request")
-            }
-        } else {
+        if !should_send_request {
             debug!(
                 "Skipped sending request for certificate {} to '{peer_ip}' ({num_sent_requests} redundant requests)",
                 fmt_id(certificate_id)
             );
         }
-        // Wait for the certificate to be fetched.
+        (should_send_request, callback_receiver)
+    }
+
+    /// Waits for the given certificate to be fetched, up to the fetch timeout.
+    async fn wait_for_certificate(
+        certificate_id: Field<N>,
+        callback_receiver: oneshot::Receiver<BatchCertificate<N>>,
+    ) -> Result<BatchCertificate<N>> {
         match tokio::time::timeout(Duration::from_millis(MAX_FETCH_TIMEOUT_IN_MS), callback_receiver).await {
             // If the certificate was fetched, return it.
             Ok(result) => Ok(result?),
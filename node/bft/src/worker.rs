@@ -34,7 +34,7 @@ use snarkvm::{
 
 use colored::Colorize;
 use indexmap::{IndexMap, IndexSet};
-use parking_lot::Mutex;
+use parking_lot::{Mutex, RwLock};
 use rand::seq::IteratorRandom;
 use std::{future::Future, net::SocketAddr, sync::Arc, time::Duration};
 use tokio::{sync::oneshot, task::JoinHandle, time::timeout};
@@ -55,6 +55,9 @@ pub struct Worker<N: Network> {
     ready: Ready<N>,
     /// The pending transmissions queue.
     pending: Arc<Pending<TransmissionID<N>, Transmission<N>>>,
+    /// The IDs of the transmissions in the ready queue that were received from peers (i.e. not submitted locally),
+    /// which are only tracked if the node does not relay transmissions.
+    peer_transmissions: Arc<RwLock<IndexSet<TransmissionID<N>>>>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
 }
@@ -79,6 +82,7 @@ impl<N: Network> Worker<N> {
             proposed_batch,
             ready: Default::default(),
             pending: Default::default(),
+            peer_transmissions: Default::default(),
             handles: Default::default(),
         })
     }
@@ -156,7 +160,8 @@ impl<N: Network> Worker<N> {
 impl<N: Network> Worker<N> {
     /// Clears the solutions from the ready queue.
     pub(super) fn clear_solutions(&self) {
-        self.ready.clear_solutions()
+        self.ready.clear_solutions();
+        self.peer_transmissions.write().retain(|id| !matches!(id, TransmissionID::Solution(..)));
     }
 }
 
@@ -213,7 +218,15 @@ impl<N: Network> Worker<N> {
 
     /// Removes up to the specified number of transmissions from the ready queue, and returns them.
     pub(crate) fn drain(&self, num_transmissions: usize) -> impl Iterator<Item = (TransmissionID<N>, Transmission<N>)> {
-        self.ready.drain(num_transmissions).into_iter()
+        let transmissions = self.ready.drain(num_transmissions);
+        // Forget the drained transmissions that were received from peers, as they are no longer in the ready queue.
+        let mut peer_transmissions = self.peer_transmissions.write();
+        if !peer_transmissions.is_empty() {
+            transmissions.keys().for_each(|id| {
+                peer_transmissions.swap_remove(id);
+            });
+        }
+        transmissions.into_iter()
     }
 
     /// Reinserts the specified transmission into the ready queue.
@@ -307,6 +320,10 @@ impl<N: Network> Worker<N> {
         };
         // If the transmission ID and transmission type matches, then insert the transmission into the ready queue.
         if is_well_formed && self.ready.insert(transmission_id, transmission) {
+            // If the node does not relay transmissions, remember the transmission was received from a peer.
+            if !self.gateway.relays_transmissions() {
+                self.peer_transmissions.write().insert(transmission_id);
+            }
             trace!(
                 "Worker {} - Added transmission '{}.{}' from '{peer_ip}'",
                 self.id,
@@ -331,8 +348,8 @@ impl<N: Network> Worker<N> {
         let transmission_id = TransmissionID::Solution(solution_id, checksum);
         // Remove the solution ID from the pending queue.
         self.pending.remove(transmission_id, Some(transmission.clone()));
-        // Check if the solution exists.
-        if self.contains_transmission(transmission_id) {
+        // Check if the solution exists, or was stored within the deduplication window.
+        if self.contains_transmission(transmission_id) || self.storage.contains_recent_transmission(transmission_id) {
             bail!("Solution '{}.{}' already exists.", fmt_id(solution_id), fmt_id(checksum).dimmed());
         }
         // Check that the solution is well-formed and unique.
@@ -363,8 +380,8 @@ impl<N: Network> Worker<N> {
         let transmission_id = TransmissionID::Transaction(transaction_id, checksum);
         // Remove the transaction from the pending queue.
         self.pending.remove(transmission_id, Some(transmission.clone()));
-        // Check if the transaction ID exists.
-        if self.contains_transmission(transmission_id) {
+        // Check if the transaction ID exists, or was stored within the deduplication window.
+        if self.contains_transmission(transmission_id) || self.storage.contains_recent_transmission(transmission_id) {
             bail!("Transaction '{}.{}' already exists.", fmt_id(transaction_id), fmt_id(checksum).dimmed());
         }
         // Check that the transaction is well-formed and unique.
@@ -498,8 +515,17 @@ impl<N: Network> Worker<N> {
     /// Sends the requested transmission to the specified peer.
     fn send_transmission_response(&self, peer_ip: SocketAddr, request: TransmissionRequest<N>) {
         let TransmissionRequest { transmission_id } = request;
-        // Attempt to retrieve the transmission.
-        if let Some(transmission) = self.get_transmission(transmission_id) {
+        // Attempt to retrieve the transmission. If the node does not relay transmissions, the ones it received
+        // from its peers are not served from its ready queue, only once they are proposed or certified.
+        let is_relayed = self.peer_transmissions.read().contains(&transmission_id);
+        let transmission = if !is_relayed || self.gateway.relays_transmissions() {
+            self.get_transmission(transmission_id)
+        } else {
+            self.storage.get_transmission(transmission_id).or_else(|| {
+                self.proposed_batch.read().as_ref().and_then(|p| p.get_transmission(transmission_id)).cloned()
+            })
+        };
+        if let Some(transmission) = transmission {
             // Send the transmission response to the peer.
             let self_ = self.clone();
             tokio::spawn(async move {
@@ -525,7 +551,7 @@ impl<N: Network> Worker<N> {
 mod tests {
     use super::*;
     use crate::helpers::CALLBACK_EXPIRATION_IN_SECS;
-    use snarkos_node_bft_ledger_service::LedgerService;
+    use snarkos_node_bft_ledger_service::{LedgerService, MockLedgerService};
     use snarkos_node_bft_storage_service::BFTMemoryService;
     use snarkvm::{
         console::{network::Network, types::Field},
@@ -552,6 +578,7 @@ mod tests {
         impl<N:Network> Transport<N> for Gateway<N> {
             fn broadcast(&self, event: Event<N>);
             async fn send(&self, peer_ip: SocketAddr, event: Event<N>) -> Option<oneshot::Receiver<io::Result<()>>>;
+            fn relays_transmissions(&self) -> bool;
         }
     }
 
@@ -633,7 +660,8 @@ mod tests {
         let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
         let committee_clone = committee.clone();
         // Setup the mock gateway and ledger.
-        let gateway = MockGateway::default();
+        let mut gateway = MockGateway::default();
+        gateway.expect_relays_transmissions().return_const(true);
         let mut mock_ledger = MockLedger::default();
         mock_ledger.expect_current_committee().returning(move || Ok(committee.clone()));
         mock_ledger.expect_get_committee_lookback_for_round().returning(move |_| Ok(committee_clone.clone()));
@@ -778,6 +806,96 @@ mod tests {
         assert!(!worker.ready.contains(transmission_id));
     }
 
+    #[tokio::test]
+    async fn test_process_solution_with_validator() {
+        let rng = &mut TestRng::default();
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Setup the mock gateway, and a ledger rejecting one of the solution IDs.
+        let gateway = MockGateway::default();
+        let rejected_id = rng.gen::<u64>().into();
+        let validator = move |transmission_id: &TransmissionID<CurrentNetwork>| match transmission_id {
+            TransmissionID::Solution(solution_id, _) => *solution_id != rejected_id,
+            _ => true,
+        };
+        let ledger: Arc<dyn LedgerService<CurrentNetwork>> =
+            Arc::new(MockLedgerService::with_validator(committee, Box::new(validator)));
+        // Initialize the storage.
+        let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
+
+        // Create the Worker.
+        let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+        let solution = Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+        let checksum = solution.to_checksum::<CurrentNetwork>().unwrap();
+
+        // Ensure the solution rejected by the validator is not added to the ready queue.
+        let result = worker.process_unconfirmed_solution(rejected_id, solution.clone()).await;
+        assert!(result.is_err());
+        assert!(!worker.ready.contains(TransmissionID::Solution(rejected_id, checksum)));
+        // Ensure any other solution is added to the ready queue.
+        let accepted_id = rng.gen::<u64>().into();
+        let result = worker.process_unconfirmed_solution(accepted_id, solution).await;
+        assert!(result.is_ok());
+        assert!(worker.ready.contains(TransmissionID::Solution(accepted_id, checksum)));
+    }
+
+    #[tokio::test]
+    async fn test_send_transmission_response_without_relay() {
+        let rng = &mut TestRng::default();
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Setup the mock gateway, which does not relay transmissions, and records the transmissions it sends.
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let sent_ = sent.clone();
+        let mut gateway = MockGateway::default();
+        gateway.expect_relays_transmissions().return_const(false);
+        gateway.expect_send().returning(move |_, event| {
+            if let Event::TransmissionResponse(response) = event {
+                sent_.lock().push(response.transmission_id);
+            }
+            None
+        });
+        let ledger: Arc<dyn LedgerService<CurrentNetwork>> = Arc::new(MockLedgerService::new(committee));
+        // Initialize the storage, with a certified transmission.
+        let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
+        let certificate = snarkvm::ledger::narwhal::batch_certificate::test_helpers::sample_batch_certificate(rng);
+        let certified_id = *certificate.transmission_ids().first().unwrap();
+        storage.testing_only_insert_certificate_testing_only(certificate);
+
+        // Create the Worker.
+        let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+        let data = |rng: &mut TestRng| Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+        // Add a solution submitted locally to the ready queue.
+        let (local_solution_id, local_solution) = (rng.gen::<u64>().into(), data(rng));
+        let local_checksum = local_solution.to_checksum::<CurrentNetwork>().unwrap();
+        let local_id = TransmissionID::Solution(local_solution_id, local_checksum);
+        worker.process_unconfirmed_solution(local_solution_id, local_solution).await.unwrap();
+        // Add a solution received from a peer to the ready queue.
+        let peer_solution = data(rng);
+        let peer_checksum = peer_solution.to_checksum::<CurrentNetwork>().unwrap();
+        let peer_id = TransmissionID::Solution(rng.gen::<u64>().into(), peer_checksum);
+        let peer_ip = SocketAddr::from(([127, 0, 0, 1], 1234));
+        worker.process_transmission_from_peer(peer_ip, peer_id, Transmission::Solution(peer_solution));
+        assert!(worker.ready.contains(peer_id));
+
+        // Request each of the transmissions.
+        for transmission_id in [local_id, peer_id, certified_id] {
+            worker.send_transmission_response(peer_ip, TransmissionRequest::new(transmission_id));
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // Ensure the local and certified transmissions are served, but not the one received from the peer.
+        let sent = sent.lock().clone();
+        assert_eq!(sent.len(), 2);
+        assert!(sent.contains(&local_id));
+        assert!(sent.contains(&certified_id));
+        assert!(!sent.contains(&peer_id));
+
+        // Ensure the transmission received from the peer is forgotten once it leaves the ready queue.
+        assert_eq!(worker.drain(2).count(), 2);
+        assert!(worker.peer_transmissions.read().is_empty());
+    }
+
     #[tokio::test]
     async fn test_process_transaction_ok() {
         let mut rng = &mut TestRng::default();
@@ -13,7 +13,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-pub(super) const COUNTER_NAMES: [&str; 2] = [bft::LEADERS_ELECTED, consensus::STALE_UNCONFIRMED_TRANSMISSIONS];
+pub(super) const COUNTER_NAMES: [&str; 4] = [
+    bft::LEADERS_ELECTED,
+    bft::DROPPED_INBOUND_EVENTS,
+    bft::COMMITTED_LEADER_MISMATCHES,
+    consensus::STALE_UNCONFIRMED_TRANSMISSIONS,
+];
 
 pub(super) const GAUGE_NAMES: [&str; 26] = [
     bft::CONNECTED,
@@ -58,6 +63,8 @@ pub mod bft {
     pub const HEIGHT: &str = "snarkos_bft_height_total";
     pub const LAST_COMMITTED_ROUND: &str = "snarkos_bft_last_committed_round";
     pub const IS_SYNCED: &str = "snarkos_bft_is_synced";
+    pub const DROPPED_INBOUND_EVENTS: &str = "snarkos_bft_dropped_inbound_events_total";
+    pub const COMMITTED_LEADER_MISMATCHES: &str = "snarkos_bft_committed_leader_mismatches_total";
 }
 
 pub mod blocks {
@@ -22,6 +22,9 @@ pub use connections::{Connection, ConnectionSide};
 mod known_peers;
 pub use known_peers::KnownPeers;
 
+mod socks5;
+pub(crate) use socks5::socks5_handshake;
+
 mod stats;
 pub use stats::Stats;
 
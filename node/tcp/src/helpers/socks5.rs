@@ -0,0 +1,144 @@
+// Copyright 2024 Aleo Network Foundation
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    io::{self, ErrorKind},
+    net::SocketAddr,
+};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+/// The SOCKS protocol version.
+const SOCKS_VERSION: u8 = 0x05;
+/// The "no authentication required" method.
+const METHOD_NO_AUTH: u8 = 0x00;
+/// The "CONNECT" command.
+const COMMAND_CONNECT: u8 = 0x01;
+/// The IPv4 address type.
+const ADDRESS_TYPE_IPV4: u8 = 0x01;
+/// The domain name address type.
+const ADDRESS_TYPE_DOMAIN: u8 = 0x03;
+/// The IPv6 address type.
+const ADDRESS_TYPE_IPV6: u8 = 0x04;
+/// The "succeeded" reply.
+const REPLY_SUCCEEDED: u8 = 0x00;
+
+/// Performs a SOCKS5 (RFC 1928) handshake over the given stream, which must already be connected to the proxy,
+/// requesting the proxy to establish a connection to the given target address. No authentication is supported.
+pub(crate) async fn socks5_handshake(stream: &mut TcpStream, target: SocketAddr) -> io::Result<()> {
+    // Greet the proxy, offering only the "no authentication" method.
+    stream.write_all(&[SOCKS_VERSION, 1, METHOD_NO_AUTH]).await?;
+    let mut reply = [0u8; 2];
+    stream.read_exact(&mut reply).await?;
+    if reply != [SOCKS_VERSION, METHOD_NO_AUTH] {
+        return Err(io::Error::new(ErrorKind::InvalidData, "SOCKS5 proxy rejected the authentication method"));
+    }
+
+    // Request a connection to the target.
+    let mut request = vec![SOCKS_VERSION, COMMAND_CONNECT, 0x00];
+    match target {
+        SocketAddr::V4(addr) => {
+            request.push(ADDRESS_TYPE_IPV4);
+            request.extend_from_slice(&addr.ip().octets());
+        }
+        SocketAddr::V6(addr) => {
+            request.push(ADDRESS_TYPE_IPV6);
+            request.extend_from_slice(&addr.ip().octets());
+        }
+    }
+    request.extend_from_slice(&target.port().to_be_bytes());
+    stream.write_all(&request).await?;
+
+    // Read the reply header.
+    let mut header = [0u8; 4];
+    stream.read_exact(&mut header).await?;
+    if header[0] != SOCKS_VERSION {
+        return Err(io::Error::new(ErrorKind::InvalidData, "Invalid SOCKS5 proxy reply"));
+    }
+    if header[1] != REPLY_SUCCEEDED {
+        return Err(io::Error::new(
+            ErrorKind::ConnectionRefused,
+            format!("SOCKS5 proxy failed to connect to {target} (reply code {})", header[1]),
+        ));
+    }
+    // Skip the bound address and port.
+    let address_len = match header[3] {
+        ADDRESS_TYPE_IPV4 => 4,
+        ADDRESS_TYPE_IPV6 => 16,
+        ADDRESS_TYPE_DOMAIN => stream.read_u8().await? as usize,
+        _ => return Err(io::Error::new(ErrorKind::InvalidData, "Invalid SOCKS5 proxy address type")),
+    };
+    let mut bound = vec![0u8; address_len + 2];
+    stream.read_exact(&mut bound).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_socks5_handshake() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy_addr = listener.local_addr().unwrap();
+        let target: SocketAddr = "10.0.0.1:5000".parse().unwrap();
+
+        // Spawn a minimal SOCKS5 proxy that accepts a single CONNECT request.
+        let proxy = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut greeting = [0u8; 3];
+            stream.read_exact(&mut greeting).await.unwrap();
+            assert_eq!(greeting, [SOCKS_VERSION, 1, METHOD_NO_AUTH]);
+            stream.write_all(&[SOCKS_VERSION, METHOD_NO_AUTH]).await.unwrap();
+
+            let mut request = [0u8; 10];
+            stream.read_exact(&mut request).await.unwrap();
+            assert_eq!(request, [SOCKS_VERSION, COMMAND_CONNECT, 0, ADDRESS_TYPE_IPV4, 10, 0, 0, 1, 0x13, 0x88]);
+            stream.write_all(&[SOCKS_VERSION, REPLY_SUCCEEDED, 0, ADDRESS_TYPE_IPV4, 0, 0, 0, 0, 0, 0]).await.unwrap();
+        });
+
+        let mut stream = TcpStream::connect(proxy_addr).await.unwrap();
+        socks5_handshake(&mut stream, target).await.unwrap();
+        proxy.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_socks5_handshake_refused() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy_addr = listener.local_addr().unwrap();
+        let target: SocketAddr = "10.0.0.1:5000".parse().unwrap();
+
+        // Spawn a minimal SOCKS5 proxy that refuses the CONNECT request.
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut greeting = [0u8; 3];
+            stream.read_exact(&mut greeting).await.unwrap();
+            stream.write_all(&[SOCKS_VERSION, METHOD_NO_AUTH]).await.unwrap();
+            let mut request = [0u8; 10];
+            stream.read_exact(&mut request).await.unwrap();
+            stream.write_all(&[SOCKS_VERSION, 0x05, 0, ADDRESS_TYPE_IPV4, 0, 0, 0, 0, 0, 0]).await.unwrap();
+        });
+
+        let mut stream = TcpStream::connect(proxy_addr).await.unwrap();
+        let error = socks5_handshake(&mut stream, target).await.unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::ConnectionRefused);
+    }
+}
@@ -42,6 +42,14 @@ where
     /// The default value is 3000ms.
     const TIMEOUT_MS: u64 = 3_000;
 
+    /// The maximum time allowed for a connection to perform a handshake, evaluated for every handshake;
+    /// it can be overridden in order for the timeout to be configurable at runtime.
+    ///
+    /// The default value is [`Handshake::TIMEOUT_MS`].
+    fn handshake_timeout_ms(&self) -> u64 {
+        Self::TIMEOUT_MS
+    }
+
     /// Prepares the node to perform specified network handshakes.
     async fn enable_handshake(&self) {
         let (from_node_sender, mut from_node_receiver) = mpsc::unbounded_channel::<ReturnableConnection>();
@@ -61,7 +69,8 @@ where
                 let node = self_clone.clone();
                 tokio::spawn(async move {
                     debug!(parent: node.tcp().span(), "shaking hands with {} as the {:?}", addr, !conn.side());
-                    let result = timeout(Duration::from_millis(Self::TIMEOUT_MS), node.perform_handshake(conn)).await;
+                    let timeout_ms = node.handshake_timeout_ms();
+                    let result = timeout(Duration::from_millis(timeout_ms), node.perform_handshake(conn)).await;
 
                     let ret = match result {
                         Ok(Ok(conn)) => {
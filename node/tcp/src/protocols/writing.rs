@@ -153,6 +153,20 @@ where
             Err(io::ErrorKind::Unsupported.into())
         }
     }
+
+    /// Returns the number of outbound messages currently queued to be sent, for each connected address.
+    /// The map is empty if [`Writing::enable_writing`] hadn't been called yet.
+    fn outbound_queue_depths(&self) -> HashMap<SocketAddr, usize> {
+        match self.tcp().protocols.writing.get() {
+            Some(handler) => handler
+                .senders
+                .read()
+                .iter()
+                .map(|(addr, sender)| (*addr, sender.max_capacity() - sender.capacity()))
+                .collect(),
+            None => Default::default(),
+        }
+    }
 }
 
 /// This trait is used to restrict access to methods that would otherwise be public in [`Writing`].
@@ -43,6 +43,7 @@ use crate::{
     Stats,
     connections::{Connection, ConnectionSide, Connections},
     protocols::{Protocol, Protocols},
+    socks5_handshake,
 };
 
 // A sequential numeric identifier assigned to `Tcp`s that were not provided with a name.
@@ -203,6 +204,16 @@ impl Tcp {
 impl Tcp {
     /// Connects to the provided `SocketAddr`.
     pub async fn connect(&self, addr: SocketAddr) -> io::Result<()> {
+        self.connect_inner(addr, None).await
+    }
+
+    /// Connects to the provided `SocketAddr` through the SOCKS5 proxy at `proxy`.
+    pub async fn connect_via_proxy(&self, addr: SocketAddr, proxy: SocketAddr) -> io::Result<()> {
+        self.connect_inner(addr, Some(proxy)).await
+    }
+
+    /// Connects to the provided `SocketAddr`, optionally through the given SOCKS5 proxy.
+    async fn connect_inner(&self, addr: SocketAddr, proxy: Option<SocketAddr>) -> io::Result<()> {
         if let Ok(listening_addr) = self.listening_addr() {
             // TODO(nkls): maybe this first check can be dropped; though it might be best to keep just in case.
             if addr == listening_addr || self.is_self_connect(addr) {
@@ -228,9 +239,17 @@ impl Tcp {
 
         let timeout_duration = Duration::from_millis(self.config().connection_timeout_ms.into());
 
-        // Bind the tcp socket to the configured listener ip if it's set.
-        // Otherwise default to the system's default interface.
-        let res = if let Some(listen_ip) = self.config().listener_ip {
+        // If a proxy is set, dial the proxy and ask it to connect to the peer on our behalf.
+        // Otherwise, bind the tcp socket to the configured listener ip if it's set,
+        // or default to the system's default interface.
+        let res = if let Some(proxy) = proxy {
+            timeout(timeout_duration, async {
+                let mut stream = TcpStream::connect(proxy).await?;
+                socks5_handshake(&mut stream, addr).await?;
+                Ok::<_, io::Error>(stream)
+            })
+            .await
+        } else if let Some(listen_ip) = self.config().listener_ip {
             let sock =
                 if listen_ip.is_ipv4() { tokio::net::TcpSocket::new_v4()? } else { tokio::net::TcpSocket::new_v6()? };
             sock.bind(SocketAddr::new(listen_ip, 0))?;